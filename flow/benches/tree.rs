@@ -0,0 +1,98 @@
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use flow::{
+    EmptyLayout, HorizontalLayout, Layout, LayoutTree, Node, Size, VerticalLayout, solve_layout,
+};
+
+/// The number of rows, and children in each row, giving
+/// `ROWS * ROWS + ROWS + 1` nodes
+const ROWS: usize = 100;
+
+/// The number of nodes in the deep tree
+const DEPTH: usize = 4000;
+
+fn tree() -> LayoutTree {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::vertical().fill());
+    tree.set_root(root).unwrap();
+    for _ in 0..ROWS {
+        let row = tree
+            .insert_child(root, Node::horizontal().fill_width())
+            .unwrap();
+        for _ in 0..ROWS {
+            tree.insert_child(row, Node::empty().fixed_width(10.0).fill_height())
+                .unwrap();
+        }
+    }
+    tree
+}
+
+/// A single chain of fit nodes, where every node's content size depends
+/// on the whole subtree below it.
+fn deep_tree() -> LayoutTree {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::vertical());
+    tree.set_root(root).unwrap();
+    let mut parent = root;
+    for _ in 0..DEPTH {
+        parent = tree.insert_child(parent, Node::vertical()).unwrap();
+    }
+    tree.insert_child(parent, Node::empty().fixed(10.0, 10.0))
+        .unwrap();
+    tree
+}
+
+fn layout() -> VerticalLayout {
+    let mut layout = VerticalLayout::new().fill();
+    for _ in 0..ROWS {
+        let mut row = HorizontalLayout::new().fill_width();
+        for _ in 0..ROWS {
+            row.push(EmptyLayout::new().fixed_width(10.0).fill_height());
+        }
+        layout.push(row);
+    }
+    layout
+}
+
+/// Compare the arena with the trait object layouts on a tree that both
+/// solve to the same result. The layouts also support margins, shrinking,
+/// directions and snapping, which the arena doesn't, so this measures the
+/// cost of the tree structure rather than two identical solvers.
+fn solve(c: &mut Criterion) {
+    let size = Size::new(1920.0, 1080.0);
+    let mut group = c.benchmark_group("solve 10k nodes");
+
+    let mut tree = tree();
+    let mut check = layout();
+    tree.solve(size).unwrap();
+    solve_layout(&mut check, size).unwrap();
+    let root = tree.root().unwrap();
+    assert_eq!(tree.get(root).unwrap().size(), check.size());
+
+    group.bench_function("LayoutTree", |b| b.iter(|| tree.solve(size).unwrap()));
+
+    // Layouts skip unchanged subtrees, so each iteration needs a new tree
+    group.bench_function("Layout", |b| {
+        b.iter_batched(
+            layout,
+            |mut layout| solve_layout(&mut layout, size).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    let mut layout = layout();
+    solve_layout(&mut layout, size).unwrap();
+    group.bench_function("Layout cached", |b| {
+        b.iter(|| solve_layout(&mut layout, size).unwrap())
+    });
+
+    group.finish();
+}
+
+fn solve_deep(c: &mut Criterion) {
+    let size = Size::new(1920.0, 1080.0);
+    let mut tree = deep_tree();
+    c.bench_function("solve 4k deep", |b| b.iter(|| tree.solve(size).unwrap()));
+}
+
+criterion_group!(benches, solve, solve_deep);
+criterion_main!(benches);
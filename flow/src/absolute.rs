@@ -0,0 +1,364 @@
+use crate::content::{fit_height, fit_width};
+use crate::{Axis, BoxConstraints, BoxSizing, Layout, LayoutDirection, Margin, SizeBounds};
+use ruby_core::{GlobalId, Position, Size};
+
+/// The distance from the edges of the parent to an absolutely
+/// positioned layout.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Insets {
+    pub left: Option<f32>,
+    pub right: Option<f32>,
+    pub top: Option<f32>,
+    pub bottom: Option<f32>,
+}
+
+/// Wraps a [`Layout`] to take it out of the normal flow of it's parent.
+///
+/// Absolutely positioned layouts are ignored when the parent calculates
+/// it's min constraints and divides flex space, instead they are placed
+/// relative to the edges of the parent using their [`Insets`]. If both the
+/// `left` and `right` insets are set, then `left` takes priority, the same
+/// goes for `top` and `bottom`. Children with a flex size fill the parent,
+/// minus the insets.
+///
+/// The wrapper has it's own id and shows up as the parent of the wrapped
+/// layout when traversing the tree, but it always shares the size and
+/// position of the wrapped layout.
+///
+/// # Example
+/// ```
+/// use flow::{AbsoluteLayout,HorizontalLayout,EmptyLayout,Position,Size,Layout};
+///
+/// let tooltip = AbsoluteLayout::new(EmptyLayout::new().fixed(50.0, 20.0))
+///     .right(10.0)
+///     .top(5.0);
+///
+/// let mut layout = HorizontalLayout::new().fixed_width(300.0);
+/// layout.push(EmptyLayout::new().fixed(100.0, 40.0));
+/// layout.push(tooltip);
+///
+/// flow::solve_layout(&mut layout,Size::unit(1000.0)).unwrap();
+/// assert_eq!(layout.size(),Size::new(300.0, 40.0));
+/// assert_eq!(layout.children()[1].position(),Position::new(240.0, 5.0));
+/// ```
+#[derive(Debug)]
+pub struct AbsoluteLayout {
+    id: GlobalId,
+    child: Box<dyn Layout>,
+    pub insets: Insets,
+}
+
+impl AbsoluteLayout {
+    pub fn new(child: impl Layout + 'static) -> Self {
+        Self {
+            id: GlobalId::new(),
+            child: Box::new(child),
+            insets: Insets::default(),
+        }
+    }
+
+    pub fn child(&self) -> &dyn Layout {
+        self.child.as_ref()
+    }
+
+    pub fn left(mut self, left: f32) -> Self {
+        self.insets.left = Some(left);
+        self
+    }
+
+    pub fn right(mut self, right: f32) -> Self {
+        self.insets.right = Some(right);
+        self
+    }
+
+    pub fn top(mut self, top: f32) -> Self {
+        self.insets.top = Some(top);
+        self
+    }
+
+    pub fn bottom(mut self, bottom: f32) -> Self {
+        self.insets.bottom = Some(bottom);
+        self
+    }
+}
+
+impl Layout for AbsoluteLayout {
+    fn id(&self) -> GlobalId {
+        self.id
+    }
+
+    fn size(&self) -> Size<f32> {
+        self.child.size()
+    }
+
+    fn set_size(&mut self, size: Size<f32>) {
+        self.child.set_size(size);
+    }
+
+    fn position(&self) -> Position<f32> {
+        self.child.position()
+    }
+
+    fn set_position(&mut self, position: Position<f32>) {
+        self.child.set_position(position);
+    }
+
+    fn intrinsic_width(&self) -> BoxSizing {
+        self.child.intrinsic_width()
+    }
+
+    fn intrinsic_height(&self) -> BoxSizing {
+        self.child.intrinsic_height()
+    }
+
+    fn set_max_width(&mut self, width: f32) {
+        self.child.set_max_width(width);
+    }
+
+    fn set_max_height(&mut self, height: f32) {
+        self.child.set_max_height(height);
+    }
+
+    fn set_min_width(&mut self, width: f32) {
+        self.child.set_min_width(width);
+    }
+
+    fn set_min_height(&mut self, height: f32) {
+        self.child.set_min_height(height);
+    }
+
+    fn constraints(&self) -> BoxConstraints {
+        self.child.constraints()
+    }
+
+    fn bounds(&self) -> SizeBounds {
+        self.child.bounds()
+    }
+
+    fn margin(&self) -> Margin {
+        self.child.margin()
+    }
+
+    fn shrink(&self) -> f32 {
+        self.child.shrink()
+    }
+
+    fn track_sizing(&self) -> Vec<(Axis, BoxSizing)> {
+        self.child.track_sizing()
+    }
+
+    fn baseline(&self) -> Option<f32> {
+        self.child.baseline()
+    }
+
+    fn direction(&self) -> LayoutDirection {
+        self.child.direction()
+    }
+
+    fn set_direction(&mut self, direction: LayoutDirection) {
+        self.child.set_direction(direction);
+    }
+
+    fn scale_factor(&self) -> Option<f32> {
+        self.child.scale_factor()
+    }
+
+    fn set_scale_factor(&mut self, scale_factor: Option<f32>) {
+        self.child.set_scale_factor(scale_factor);
+    }
+
+    fn min_content_width(&self, height: Option<f32>) -> f32 {
+        self.child.min_content_width(height)
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.child.max_content_width(height)
+    }
+
+    fn min_content_height(&self, width: Option<f32>) -> f32 {
+        self.child.min_content_height(width)
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.child.max_content_height(width)
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.child.is_interactive()
+    }
+
+    fn absolute_insets(&self) -> Option<Insets> {
+        Some(self.insets)
+    }
+
+    fn children(&self) -> Vec<&dyn Layout> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Layout> {
+        vec![self.child.as_mut()]
+    }
+
+    // The wrapped layout can't be removed, since the wrapper
+    // always needs a child
+    fn replace_child(&mut self, id: GlobalId, child: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        if self.child.id() != id {
+            return None;
+        }
+        Some(std::mem::replace(&mut self.child, child))
+    }
+
+    fn mark_dirty(&mut self) {
+        self.child.mark_dirty();
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.child.is_dirty()
+    }
+
+    fn was_recomputed(&self) -> bool {
+        self.child.was_recomputed()
+    }
+
+    fn solve_max_constraints(&mut self) {
+        self.child.solve_max_constraints();
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        self.child.solve_min_constraints()
+    }
+
+    fn update_size(&mut self) {
+        self.child.update_size();
+    }
+
+    fn position_children(&mut self) {
+        self.child.position_children();
+    }
+
+    fn snap_to_pixels(&mut self) {
+        self.child.snap_to_pixels();
+    }
+}
+
+/// Returns `true` if the layout is part of the normal flow of
+/// it's parent.
+pub(crate) fn in_flow(layout: &dyn Layout) -> bool {
+    layout.absolute_insets().is_none()
+}
+
+/// Solve the max constraints of an absolutely positioned layout, once
+/// the size of it's parent is known. The min constraints of the layout
+/// are solved again afterwards, since the min pass of the parent ran
+/// before the max constraints were known.
+pub(crate) fn constrain_absolute(layout: &mut dyn Layout, parent: Size<f32>) {
+    let Some(insets) = layout.absolute_insets() else {
+        return;
+    };
+
+    match layout.intrinsic_width() {
+        BoxSizing::Fit => {
+            let insets = insets.left.unwrap_or_default() + insets.right.unwrap_or_default();
+            let width = fit_width(layout, parent.width - insets, None);
+            layout.set_max_width(width);
+        }
+        BoxSizing::Fixed(width) => layout.set_max_width(width),
+        BoxSizing::Flex(_) => {
+            let insets = insets.left.unwrap_or_default() + insets.right.unwrap_or_default();
+            layout.set_max_width(parent.width - insets);
+        }
+        BoxSizing::Percent(percent) => {
+            layout.set_max_width(BoxSizing::percent_of(percent, parent.width));
+        }
+    }
+
+    match layout.intrinsic_height() {
+        BoxSizing::Fit => {
+            let insets = insets.top.unwrap_or_default() + insets.bottom.unwrap_or_default();
+            let width = Some(layout.constraints().max_width);
+            let height = fit_height(layout, parent.height - insets, width);
+            layout.set_max_height(height);
+        }
+        BoxSizing::Fixed(height) => layout.set_max_height(height),
+        BoxSizing::Flex(_) => {
+            let insets = insets.top.unwrap_or_default() + insets.bottom.unwrap_or_default();
+            layout.set_max_height(parent.height - insets);
+        }
+        BoxSizing::Percent(percent) => {
+            layout.set_max_height(BoxSizing::percent_of(percent, parent.height));
+        }
+    }
+
+    layout.solve_max_constraints();
+    layout.solve_min_constraints();
+}
+
+/// Position an absolutely positioned layout inside it's parent.
+pub(crate) fn position_absolute(
+    layout: &mut dyn Layout,
+    position: Position<f32>,
+    parent: Size<f32>,
+) {
+    let Some(insets) = layout.absolute_insets() else {
+        return;
+    };
+    let size = layout.size();
+
+    let x = match (insets.left, insets.right) {
+        (Some(left), _) => position.x + left,
+        (None, Some(right)) => position.x + parent.width - size.width - right,
+        (None, None) => position.x,
+    };
+
+    let y = match (insets.top, insets.bottom) {
+        (Some(top), _) => position.y + top,
+        (None, Some(bottom)) => position.y + parent.height - size.height - bottom,
+        (None, None) => position.y,
+    };
+
+    let x = layout
+        .direction()
+        .place(x, size.width, position.x, parent.width);
+    layout.set_position(Position { x, y });
+    layout.position_children();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EmptyLayout;
+
+    #[test]
+    fn flex_fills_parent_minus_insets() {
+        let mut layout = AbsoluteLayout::new(EmptyLayout::new().fill())
+            .left(10.0)
+            .right(20.0)
+            .top(5.0);
+        constrain_absolute(&mut layout, Size::new(200.0, 100.0));
+
+        assert_eq!(layout.constraints().max_width, 170.0);
+        assert_eq!(layout.constraints().max_height, 95.0);
+    }
+
+    #[test]
+    fn left_inset_takes_priority() {
+        let mut layout = AbsoluteLayout::new(EmptyLayout::new().fixed(20.0, 20.0))
+            .left(10.0)
+            .right(20.0);
+        layout.update_size();
+        position_absolute(&mut layout, Position::new(50.0, 50.0), Size::unit(100.0));
+
+        assert_eq!(layout.position(), Position::new(60.0, 50.0));
+    }
+
+    #[test]
+    fn bottom_right_insets() {
+        let mut layout = AbsoluteLayout::new(EmptyLayout::new().fixed(20.0, 10.0))
+            .right(5.0)
+            .bottom(5.0);
+        layout.update_size();
+        position_absolute(&mut layout, Position::new(0.0, 0.0), Size::unit(100.0));
+
+        assert_eq!(layout.position(), Position::new(75.0, 85.0));
+    }
+}
@@ -1,4 +1,12 @@
-use crate::{BoxConstraints, BoxSizing, Layout, Padding, impl_layout, impl_padding, impl_size};
+use crate::absolute::{constrain_absolute, in_flow, position_absolute};
+use crate::cache::LayoutCache;
+use crate::content::{ContentSize, outer_height, outer_width};
+use crate::snap::snap_edges;
+use crate::{
+    BoxConstraints, BoxSizing, CrossAxisAlignment, Layout, LayoutDirection, MainAxisAlignment,
+    Margin, Padding, SizeBounds, constrain_aspect_ratio, impl_alignment, impl_layout, impl_margin,
+    impl_padding, impl_shrink, impl_size,
+};
 use ruby_core::{GlobalId, Position, Size};
 
 #[derive(Debug)]
@@ -6,11 +14,24 @@ pub struct BlockLayout {
     id: GlobalId,
     pub intrinsic_width: BoxSizing,
     pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    /// Skip this layout and it's children when hit testing
+    pub non_interactive: bool,
+    pub margin: Margin,
+    /// How much the layout shrinks, relative to it's siblings, when it's
+    /// parent runs out of space. Defaults to zero, so layouts keep their
+    /// size unless they opt in.
+    pub shrink: f32,
+    direction: LayoutDirection,
+    scale_factor: Option<f32>,
     pub padding: Padding,
+    pub main_axis_alignment: MainAxisAlignment,
+    pub cross_axis_alignment: CrossAxisAlignment,
     size: Size<f32>,
     position: Position<f32>,
     child: Box<dyn Layout>,
     constraints: BoxConstraints,
+    cache: LayoutCache,
 }
 
 impl BlockLayout {
@@ -19,11 +40,20 @@ impl BlockLayout {
             id: GlobalId::new(),
             intrinsic_width: BoxSizing::default(),
             intrinsic_height: BoxSizing::default(),
+            bounds: SizeBounds::default(),
+            non_interactive: false,
+            margin: Margin::default(),
+            shrink: 0.0,
+            direction: LayoutDirection::default(),
+            scale_factor: None,
             padding: Padding::default(),
+            main_axis_alignment: MainAxisAlignment::default(),
+            cross_axis_alignment: CrossAxisAlignment::default(),
             size: Size::default(),
             position: Position::default(),
             child: Box::new(child),
             constraints: BoxConstraints::new(),
+            cache: LayoutCache::default(),
         }
     }
 
@@ -31,21 +61,65 @@ impl BlockLayout {
         self.child.as_ref()
     }
 
+    /// Get the content width of the layout, which is the width of the
+    /// child
+    fn measure_width(&self, size: ContentSize, height: Option<f32>) -> f32 {
+        self.intrinsic_width.or_content(|| {
+            let height = height.map(|height| height - self.padding.vertical());
+            let width = outer_width(self.child.as_ref(), size, height);
+            self.bounds.clamp_width(width + self.padding.horizontal())
+        })
+    }
+
+    /// Get the content height of the layout, which is the height of the
+    /// child
+    fn measure_height(&self, size: ContentSize, width: Option<f32>) -> f32 {
+        self.intrinsic_height.or_content(|| {
+            let width = width.map(|width| width - self.padding.horizontal());
+            let height = outer_height(self.child.as_ref(), size, width);
+            self.bounds.clamp_height(height + self.padding.vertical())
+        })
+    }
+
     impl_size!();
+    impl_margin!();
+    impl_shrink!();
     impl_padding!();
+    impl_alignment!();
 }
 
 impl Layout for BlockLayout {
     fn solve_max_constraints(&mut self) {
+        if self.cache.skip(self.constraints) {
+            return;
+        }
+
+        self.child.set_direction(self.direction);
+        self.child.set_scale_factor(self.scale_factor);
+
+        constrain_aspect_ratio(self);
+        // Absolutely positioned children are constrained once the size
+        // of this layout is known
+        if !in_flow(self.child.as_ref()) {
+            return;
+        }
+
+        let margin = self.child.margin();
         match self.child.intrinsic_width() {
             BoxSizing::Fixed(width) => {
                 self.child.set_max_width(width);
             }
             BoxSizing::Fit | BoxSizing::Flex(_) => {
                 let padding = self.padding.left + self.padding.right;
-                let width = self.constraints.max_width - padding as f32;
+                let width = self.constraints.max_width - padding as f32 - margin.horizontal();
                 self.child.set_max_width(width);
             }
+            BoxSizing::Percent(percent) => {
+                let padding = self.padding.left + self.padding.right;
+                let width = self.constraints.max_width - padding as f32;
+                self.child
+                    .set_max_width(BoxSizing::percent_of(percent, width));
+            }
         }
 
         match self.child.intrinsic_height() {
@@ -54,21 +128,38 @@ impl Layout for BlockLayout {
             }
             BoxSizing::Fit | BoxSizing::Flex(_) => {
                 let padding = self.padding.top + self.padding.bottom;
-                let height = self.constraints.max_height - padding as f32;
+                let height = self.constraints.max_height - padding as f32 - margin.vertical();
                 self.child.set_max_height(height);
             }
+            BoxSizing::Percent(percent) => {
+                let padding = self.padding.top + self.padding.bottom;
+                let height = self.constraints.max_height - padding as f32;
+                self.child
+                    .set_max_height(BoxSizing::percent_of(percent, height));
+            }
         }
 
         self.child.solve_max_constraints();
     }
 
     fn solve_min_constraints(&mut self) -> (f32, f32) {
-        let (min_width, min_height) = self.child.solve_min_constraints();
+        if !self.cache.recomputed() {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        let (mut min_width, mut min_height) = self.child.solve_min_constraints();
+        if in_flow(self.child.as_ref()) {
+            min_width += self.child.margin().horizontal();
+            min_height += self.child.margin().vertical();
+        } else {
+            (min_width, min_height) = (0.0, 0.0);
+        }
 
         // TODO add padding in the function body not the match statement
         match self.intrinsic_width() {
             BoxSizing::Flex(_) => self.set_min_width(min_width),
             BoxSizing::Fixed(width) => self.set_min_width(width),
+            BoxSizing::Percent(_) => self.set_min_width(self.constraints.max_width),
             BoxSizing::Fit => {
                 let padding = self.padding.left + self.padding.right;
                 let width = min_width + padding as f32;
@@ -79,6 +170,7 @@ impl Layout for BlockLayout {
         match self.intrinsic_height() {
             BoxSizing::Flex(_) => self.set_min_height(min_height),
             BoxSizing::Fixed(height) => self.set_min_height(height),
+            BoxSizing::Percent(_) => self.set_min_height(self.constraints.max_height),
             BoxSizing::Fit => {
                 let padding = self.padding.top + self.padding.bottom;
                 let height = min_height + padding as f32;
@@ -86,30 +178,102 @@ impl Layout for BlockLayout {
             }
         }
 
+        constrain_aspect_ratio(self);
         (self.constraints.min_width, self.constraints.min_height)
     }
 
     fn update_size(&mut self) {
+        if !self.cache.begin_update() {
+            return;
+        }
+
         match self.intrinsic_width {
             BoxSizing::Fit => self.size.width = self.constraints.min_width,
             BoxSizing::Fixed(width) => self.size.width = width,
-            BoxSizing::Flex(_) => self.size.width = self.constraints.max_width,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.width = self.constraints.max_width
+            }
         }
 
         match self.intrinsic_height {
             BoxSizing::Fit => self.size.height = self.constraints.min_height,
             BoxSizing::Fixed(height) => self.size.height = height,
-            BoxSizing::Flex(_) => self.size.height = self.constraints.max_height,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.height = self.constraints.max_height
+            }
         }
 
+        constrain_absolute(self.child.as_mut(), self.size);
         self.child.update_size();
     }
 
     fn position_children(&mut self) {
-        let x = self.position.x + self.padding.left as f32;
-        let y = self.position.y + self.padding.top as f32;
+        if self.cache.skip_position(self.position) {
+            return;
+        }
+
+        if !in_flow(self.child.as_ref()) {
+            position_absolute(self.child.as_mut(), self.position, self.size);
+            return;
+        }
+
+        let margin = self.child.margin();
+        let padding = self.padding.left + self.padding.right;
+        let free_space =
+            self.size.width - padding as f32 - self.child.size().width - margin.horizontal();
+        let (offset, _) = self.main_axis_alignment.distribute(free_space, 1);
+        let x = self.position.x + self.padding.left as f32 + margin.left as f32 + offset;
+
+        let padding = self.padding.top + self.padding.bottom;
+        let free_space =
+            self.size.height - padding as f32 - self.child.size().height - margin.vertical();
+        let offset = self.cross_axis_alignment.offset(free_space);
+        let y = self.position.y + self.padding.top as f32 + margin.top as f32 + offset;
 
+        let width = self.child.size().width;
+        let x = self
+            .direction
+            .place(x, width, self.position.x, self.size.width);
         self.child.set_position(Position { x, y });
+        self.child.position_children();
+    }
+
+    fn snap_to_pixels(&mut self) {
+        snap_edges(self);
+        self.child.snap_to_pixels();
+    }
+
+    fn min_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Min, height)
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Max, height)
+    }
+
+    fn min_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Min, width)
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Max, width)
+    }
+
+    fn children(&self) -> Vec<&dyn Layout> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Layout> {
+        self.mark_dirty();
+        vec![self.child.as_mut()]
+    }
+
+    fn replace_child(&mut self, id: GlobalId, child: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        if self.child.id() != id {
+            return None;
+        }
+        self.mark_dirty();
+        Some(std::mem::replace(&mut self.child, child))
     }
 
     impl_layout!();
@@ -118,9 +282,15 @@ impl Layout for BlockLayout {
 #[cfg(test)]
 mod tests{
     use super::*;
+    use crate::EmptyLayout;
     
     #[test]
     fn set_child_position(){
+        let mut layout = BlockLayout::new(EmptyLayout::new());
+        layout.padding = Padding::new(24, 0, 16, 0);
+        layout.set_position(Position::new(100.0, 50.0));
+        layout.position_children();
 
+        assert_eq!(layout.child().position(), Position::new(124.0, 66.0));
     }
 }
@@ -0,0 +1,152 @@
+use crate::{BoxConstraints, Layout};
+use ruby_core::{Position, Size};
+
+/// The state used to skip solving a layout when nothing has changed
+/// since the last time it was solved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LayoutCache {
+    dirty: bool,
+    recomputed: bool,
+    /// Whether the layout is in the middle of being solved, during which
+    /// the max constraints may be solved more than once
+    solving: bool,
+    /// The max constraints the layout was last solved with
+    max_size: Option<Size<f32>>,
+    /// The position the children were last positioned at
+    position: Option<Position<f32>>,
+}
+
+impl Default for LayoutCache {
+    fn default() -> Self {
+        // Layouts that haven't been solved yet are treated as recomputed,
+        // so that each pass can be run on it's own
+        Self {
+            dirty: false,
+            recomputed: true,
+            solving: false,
+            max_size: None,
+            position: None,
+        }
+    }
+}
+
+impl LayoutCache {
+    pub(crate) fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub(crate) fn recomputed(&self) -> bool {
+        self.recomputed
+    }
+
+    /// Returns `true` if the layout is clean and the max constraints
+    /// haven't changed, in which case the whole subtree can be skipped.
+    /// Otherwise the layout is marked as recomputed and clean.
+    pub(crate) fn skip(&mut self, constraints: BoxConstraints) -> bool {
+        let max_size = Size::new(constraints.max_width, constraints.max_height);
+        if !self.dirty && self.max_size == Some(max_size) {
+            if !self.solving {
+                self.recomputed = false;
+            }
+            return true;
+        }
+
+        self.dirty = false;
+        self.recomputed = true;
+        self.solving = true;
+        self.max_size = Some(max_size);
+        false
+    }
+
+    /// Called at the start of `update_size`, which is the last time the
+    /// constraints of a layout are solved. Returns `false` if the layout
+    /// was skipped and it's size is already known.
+    pub(crate) fn begin_update(&mut self) -> bool {
+        self.solving = false;
+        self.recomputed
+    }
+
+    /// Returns `true` if the layout was skipped and hasn't moved, in
+    /// which case the children are already in the right place.
+    pub(crate) fn skip_position(&mut self, position: Position<f32>) -> bool {
+        let skip = !self.recomputed && self.position == Some(position);
+        self.position = Some(position);
+        skip
+    }
+}
+
+/// Count the layouts that were solved in the last call to
+/// [`solve_layout`], rather than reused from the cache.
+///
+/// [`solve_layout`]: crate::solve_layout
+pub fn count_recomputed(layout: &dyn Layout) -> usize {
+    if !layout.was_recomputed() {
+        return 0;
+    }
+
+    let children: usize = layout.children().into_iter().map(count_recomputed).sum();
+    children + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraints(width: f32, height: f32) -> BoxConstraints {
+        BoxConstraints {
+            max_width: width,
+            max_height: height,
+            ..BoxConstraints::default()
+        }
+    }
+
+    #[test]
+    fn skip_same_constraints() {
+        let mut cache = LayoutCache::default();
+        assert!(!cache.skip(constraints(100.0, 50.0)));
+        assert!(cache.recomputed());
+        cache.begin_update();
+
+        assert!(cache.skip(constraints(100.0, 50.0)));
+        assert!(!cache.recomputed());
+        assert!(!cache.skip(constraints(120.0, 50.0)));
+    }
+
+    #[test]
+    fn stay_recomputed_while_solving() {
+        let mut cache = LayoutCache::default();
+        cache.skip(constraints(100.0, 50.0));
+        assert!(cache.skip(constraints(100.0, 50.0)));
+        assert!(cache.begin_update());
+
+        assert!(cache.skip(constraints(100.0, 50.0)));
+        assert!(!cache.begin_update());
+    }
+
+    #[test]
+    fn dirty_is_not_skipped() {
+        let mut cache = LayoutCache::default();
+        cache.skip(constraints(100.0, 50.0));
+        cache.mark_dirty();
+
+        assert!(!cache.skip(constraints(100.0, 50.0)));
+        assert!(!cache.is_dirty());
+    }
+
+    #[test]
+    fn skip_position_when_unchanged() {
+        let mut cache = LayoutCache::default();
+        cache.skip(constraints(100.0, 50.0));
+        cache.begin_update();
+        assert!(!cache.skip_position(Position::new(10.0, 10.0)));
+
+        cache.skip(constraints(100.0, 50.0));
+        cache.begin_update();
+        assert!(cache.skip_position(Position::new(10.0, 10.0)));
+        assert!(!cache.skip_position(Position::new(20.0, 10.0)));
+    }
+}
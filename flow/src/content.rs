@@ -0,0 +1,110 @@
+use crate::Layout;
+use crate::absolute::in_flow;
+
+/// Which of the intrinsic content sizes to query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContentSize {
+    /// The smallest size without overflowing
+    Min,
+    /// The size with unlimited space
+    Max,
+}
+
+/// Get the content width of a child, including it's margin. Absolutely
+/// positioned children don't take up any space.
+pub(crate) fn outer_width(child: &dyn Layout, size: ContentSize, height: Option<f32>) -> f32 {
+    if !in_flow(child) {
+        return 0.0;
+    }
+
+    let margin = child.margin();
+    let height = height.map(|height| (height - margin.vertical()).max(0.0));
+    let width = match size {
+        ContentSize::Min => child.min_content_width(height),
+        ContentSize::Max => child.max_content_width(height),
+    };
+    width + margin.horizontal()
+}
+
+/// Get the content height of a child, including it's margin. Absolutely
+/// positioned children don't take up any space.
+pub(crate) fn outer_height(child: &dyn Layout, size: ContentSize, width: Option<f32>) -> f32 {
+    if !in_flow(child) {
+        return 0.0;
+    }
+
+    let margin = child.margin();
+    let width = width.map(|width| (width - margin.horizontal()).max(0.0));
+    let height = match size {
+        ContentSize::Min => child.min_content_height(width),
+        ContentSize::Max => child.max_content_height(width),
+    };
+    height + margin.vertical()
+}
+
+/// Share the `space` between children that fit their content, given the
+/// min and max content size of each child. Every child gets at least it's
+/// min content size and the rest of the space is shared in proportion to
+/// how much each child can grow, up to it's max content size.
+pub(crate) fn share_fit_space(space: f32, sizes: &[(f32, f32)]) -> Vec<f32> {
+    let min: f32 = sizes.iter().map(|(min, _)| min).sum();
+    let max: f32 = sizes.iter().map(|(min, max)| max.max(*min)).sum();
+    let growth = if max > min && max.is_finite() {
+        ((space - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    sizes
+        .iter()
+        .map(|&(min, max)| min + (max.max(min) - min) * growth)
+        .collect()
+}
+
+/// Get the max width of a child that fits it's content, which is it's max
+/// content width limited to the `available` width. The child is never
+/// given less than it's min content width.
+pub(crate) fn fit_width(child: &dyn Layout, available: f32, height: Option<f32>) -> f32 {
+    let width = child.max_content_width(height).min(available);
+    width.max(child.min_content_width(height))
+}
+
+/// Get the max height of a child that fits it's content, which is it's max
+/// content height limited to the `available` height. The child is never
+/// given less than it's min content height.
+pub(crate) fn fit_height(child: &dyn Layout, available: f32, width: Option<f32>) -> f32 {
+    let height = child.max_content_height(width).min(available);
+    height.max(child.min_content_height(width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AbsoluteLayout, EmptyLayout, Margin};
+
+    #[test]
+    fn include_margin() {
+        let child = EmptyLayout::new()
+            .fixed(50.0, 20.0)
+            .margin(Margin::sides(5, 10));
+
+        assert_eq!(outer_width(&child, ContentSize::Min, None), 60.0);
+        assert_eq!(outer_height(&child, ContentSize::Max, None), 40.0);
+    }
+
+    #[test]
+    fn share_space_between_min_and_max() {
+        let sizes = [(50.0, 250.0), (100.0, 200.0)];
+        assert_eq!(share_fit_space(1000.0, &sizes), [250.0, 200.0]);
+        assert_eq!(share_fit_space(240.0, &sizes), [110.0, 130.0]);
+        assert_eq!(share_fit_space(100.0, &sizes), [50.0, 100.0]);
+    }
+
+    #[test]
+    fn absolute_takes_no_space() {
+        let child = AbsoluteLayout::new(EmptyLayout::new().fixed(50.0, 20.0));
+
+        assert_eq!(outer_width(&child, ContentSize::Max, None), 0.0);
+        assert_eq!(outer_height(&child, ContentSize::Max, None), 0.0);
+    }
+}
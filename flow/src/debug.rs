@@ -0,0 +1,198 @@
+//! Tools for inspecting solved layouts.
+//!
+//! # Example
+//! ```
+//! use flow::{EmptyLayout,HorizontalLayout,Size};
+//!
+//! let mut layout = HorizontalLayout::new();
+//! layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+//! flow::solve_layout(&mut layout,Size::unit(1000.0)).unwrap();
+//!
+//! println!("{}", flow::debug::dump_tree(&layout));
+//! ```
+use crate::{BoxConstraints, Layout};
+use std::fmt::Write;
+
+/// Get an indented outline of a layout and all of it's children, with
+/// the type, id, intrinsic size, constraints, size and position of each
+/// layout on it's own line.
+///
+/// ```text
+/// HorizontalLayout GlobalId(1) width: Fit height: Fit min: 50x20 max: 1000x1000 size: 50x20 at (0, 0)
+///   EmptyLayout GlobalId(2) width: Fixed(50.0) height: Fixed(20.0) min: 50x20 max: 50x20 size: 50x20 at (0, 0)
+/// ```
+pub fn dump_tree(layout: &dyn Layout) -> String {
+    let mut output = String::new();
+    crate::visit(layout, &mut |layout, depth| {
+        let constraints = layout.constraints();
+        let size = layout.size();
+        let position = layout.position();
+        writeln!(
+            output,
+            "{:indent$}{} {:?} width: {:?} height: {:?} min: {}x{} max: {}x{} size: {}x{} at ({}, {})",
+            "",
+            short_name(layout),
+            layout.id(),
+            layout.intrinsic_width(),
+            layout.intrinsic_height(),
+            constraints.min_width,
+            constraints.min_height,
+            constraints.max_width,
+            constraints.max_height,
+            size.width,
+            size.height,
+            position.x,
+            position.y,
+            indent = depth * 2,
+        )
+        .unwrap();
+    });
+    output
+}
+
+/// Get a layout and all of it's children as pretty printed JSON, with the
+/// same details as [`dump_tree`]. Sizes that aren't finite, like an
+/// unlimited max width, are written as `null`.
+///
+/// Each layout also has a path from the root, the root is `"0"` and the
+/// second child of the root is `"0/1"`. Ids are different every run, use
+/// [`dump_tree_json_stable`] to leave them out and compare dumps as text.
+pub fn dump_tree_json(layout: &dyn Layout) -> String {
+    let mut output = String::new();
+    write_json(layout, "0", 0, true, &mut output);
+    output.push('\n');
+    output
+}
+
+/// Get the same JSON as [`dump_tree_json`] without the ids, so that
+/// dumps of the same tree are identical.
+pub fn dump_tree_json_stable(layout: &dyn Layout) -> String {
+    let mut output = String::new();
+    write_json(layout, "0", 0, false, &mut output);
+    output.push('\n');
+    output
+}
+
+fn write_json(layout: &dyn Layout, path: &str, depth: usize, ids: bool, output: &mut String) {
+    let indent = "  ".repeat(depth);
+    let size = layout.size();
+    let position = layout.position();
+
+    writeln!(output, "{{").unwrap();
+    writeln!(
+        output,
+        "{indent}  \"type\": {},",
+        string(short_name(layout))
+    )
+    .unwrap();
+    if ids {
+        let id = string(&format!("{:?}", layout.id()));
+        writeln!(output, "{indent}  \"id\": {id},").unwrap();
+    }
+    writeln!(output, "{indent}  \"path\": {},", string(path)).unwrap();
+    let width = string(&format!("{:?}", layout.intrinsic_width()));
+    writeln!(output, "{indent}  \"intrinsic_width\": {width},").unwrap();
+    let height = string(&format!("{:?}", layout.intrinsic_height()));
+    writeln!(output, "{indent}  \"intrinsic_height\": {height},").unwrap();
+    let constraints = constraints_json(layout.constraints());
+    writeln!(output, "{indent}  \"constraints\": {constraints},").unwrap();
+    writeln!(
+        output,
+        "{indent}  \"size\": {{\"width\": {}, \"height\": {}}},",
+        number(size.width),
+        number(size.height)
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "{indent}  \"position\": {{\"x\": {}, \"y\": {}}},",
+        number(position.x),
+        number(position.y)
+    )
+    .unwrap();
+
+    let children = layout.children();
+    if children.is_empty() {
+        writeln!(output, "{indent}  \"children\": []").unwrap();
+    } else {
+        writeln!(output, "{indent}  \"children\": [").unwrap();
+        for (i, child) in children.into_iter().enumerate() {
+            if i > 0 {
+                output.push_str(",\n");
+            }
+            write!(output, "{indent}    ").unwrap();
+            write_json(child, &format!("{path}/{i}"), depth + 2, ids, output);
+        }
+        writeln!(output).unwrap();
+        writeln!(output, "{indent}  ]").unwrap();
+    }
+    write!(output, "{indent}}}").unwrap();
+}
+
+fn constraints_json(constraints: BoxConstraints) -> String {
+    format!(
+        "{{\"min_width\": {}, \"max_width\": {}, \"min_height\": {}, \"max_height\": {}}}",
+        number(constraints.min_width),
+        number(constraints.max_width),
+        number(constraints.min_height),
+        number(constraints.max_height)
+    )
+}
+
+/// Format a number as JSON, which has no way of writing infinity or NaN.
+fn number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        String::from("null")
+    }
+}
+
+/// Format a string as JSON, escaping quotes, backslashes and control
+/// characters.
+fn string(value: &str) -> String {
+    let mut output = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            c if c.is_control() => write!(output, "\\u{:04x}", c as u32).unwrap(),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// Get the name of the layout's type without the module path.
+fn short_name(layout: &dyn Layout) -> &'static str {
+    let name = layout.type_name();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EmptyLayout, HorizontalLayout};
+
+    #[test]
+    fn short_type_name() {
+        assert_eq!(short_name(&EmptyLayout::new()), "EmptyLayout");
+        assert_eq!(short_name(&HorizontalLayout::new()), "HorizontalLayout");
+    }
+
+    #[test]
+    fn non_finite_numbers_are_null() {
+        assert_eq!(number(20.5), "20.5");
+        assert_eq!(number(f32::INFINITY), "null");
+        assert_eq!(number(f32::NAN), "null");
+    }
+
+    #[test]
+    fn escape_strings() {
+        assert_eq!(string("Flex(1.0)"), r#""Flex(1.0)""#);
+        assert_eq!(string(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(string("a\nb\u{1}"), r#""a\nb\u0001""#);
+    }
+}
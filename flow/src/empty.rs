@@ -1,4 +1,8 @@
-use crate::{BoxConstraints, BoxSizing, Layout, Position, Size, impl_layout, impl_size};
+use crate::cache::LayoutCache;
+use crate::{
+    BoxConstraints, BoxSizing, Layout, LayoutDirection, Margin, Position, Size, SizeBounds,
+    constrain_aspect_ratio, impl_layout, impl_margin, impl_shrink, impl_size,
+};
 use ruby_core::GlobalId;
 
 #[derive(Debug, Default)]
@@ -7,8 +11,19 @@ pub struct EmptyLayout {
     size: Size<f32>,
     position: Position<f32>,
     constraints: BoxConstraints,
+    cache: LayoutCache,
     pub intrinsic_width: BoxSizing,
     pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    /// Skip this layout and it's children when hit testing
+    pub non_interactive: bool,
+    pub margin: Margin,
+    /// How much the layout shrinks, relative to it's siblings, when it's
+    /// parent runs out of space. Defaults to zero, so layouts keep their
+    /// size unless they opt in.
+    pub shrink: f32,
+    direction: LayoutDirection,
+    scale_factor: Option<f32>,
 }
 
 impl EmptyLayout {
@@ -17,38 +32,79 @@ impl EmptyLayout {
     }
 
     impl_size!();
+    impl_margin!();
+    impl_shrink!();
 }
 
 impl Layout for EmptyLayout {
-    fn solve_max_constraints(&mut self) {}
+    fn solve_max_constraints(&mut self) {
+        if self.cache.skip(self.constraints) {
+            return;
+        }
+
+        constrain_aspect_ratio(self);
+    }
 
     fn position_children(&mut self) {}
 
+    fn min_content_width(&self, _height: Option<f32>) -> f32 {
+        self.intrinsic_width
+            .or_content(|| self.bounds.clamp_width(0.0))
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.min_content_width(height)
+    }
+
+    fn min_content_height(&self, _width: Option<f32>) -> f32 {
+        self.intrinsic_height
+            .or_content(|| self.bounds.clamp_height(0.0))
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.min_content_height(width)
+    }
+
     fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if !self.cache.recomputed() {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
         match self.intrinsic_width {
             BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_width(0.0),
             BoxSizing::Fixed(width) => self.set_min_width(width),
+            BoxSizing::Percent(_) => self.set_min_width(self.constraints.max_width),
         }
 
         match self.intrinsic_height {
             BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_height(0.0),
             BoxSizing::Fixed(height) => self.set_min_height(height),
+            BoxSizing::Percent(_) => self.set_min_height(self.constraints.max_height),
         }
 
+        constrain_aspect_ratio(self);
         (self.constraints.min_width, self.constraints.min_height)
     }
 
     fn update_size(&mut self) {
+        if !self.cache.begin_update() {
+            return;
+        }
+
         match self.intrinsic_width {
             BoxSizing::Fit => self.size.width = self.constraints.min_width,
             BoxSizing::Fixed(width) => self.size.width = width,
-            BoxSizing::Flex(_) => self.size.width = self.constraints.max_width,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.width = self.constraints.max_width
+            }
         }
 
         match self.intrinsic_height {
             BoxSizing::Fit => self.size.height = self.constraints.min_height,
             BoxSizing::Fixed(height) => self.size.height = height,
-            BoxSizing::Flex(_) => self.size.height = self.constraints.max_height,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.height = self.constraints.max_height
+            }
         }
     }
 
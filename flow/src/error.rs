@@ -0,0 +1,245 @@
+use crate::{Axis, BoxSizing, Layout, absolute::in_flow};
+use ruby_core::{GlobalId, Position, Size};
+
+/// The amount a child is allowed to overflow by, to account for
+/// floating point errors.
+const OVERFLOW_TOLERANCE: f32 = 0.01;
+
+/// An error that leaves a layout in an invalid state.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum LayoutError {
+    #[error("layout {id:?} has a negative {axis} size of {size}")]
+    NegativeSize { id: GlobalId, axis: Axis, size: f32 },
+    #[error("layout {id:?} has a {axis} size of {size}")]
+    NonFiniteSize { id: GlobalId, axis: Axis, size: f32 },
+    /// Flex weights have to be positive and finite.
+    #[error("layout {id:?} has an invalid {axis} flex weight of {flex}")]
+    InvalidFlex { id: GlobalId, axis: Axis, flex: f32 },
+}
+
+/// A problem with a solved layout that doesn't stop it from being
+/// drawn.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum LayoutWarning {
+    /// A child extends past the edges of it's parent.
+    #[error("layout {id:?} overflows it's parent {parent:?} by {amount} along the {axis} axis")]
+    Overflow {
+        id: GlobalId,
+        parent: GlobalId,
+        axis: Axis,
+        amount: f32,
+    },
+}
+
+/// Check a solved layout, and all of it's children, for invalid sizes
+/// and overflow.
+///
+/// Returns the first [`LayoutError`] found, otherwise a list of every
+/// child that overflows it's parent. Overflow is measured against the
+/// edges of the parent, so children may overlap the padding. Absolutely
+/// positioned children are not checked for overflow.
+pub fn validate_layout(layout: &dyn Layout) -> Result<Vec<LayoutWarning>, LayoutError> {
+    let mut warnings = vec![];
+    validate(layout, &mut warnings)?;
+    Ok(warnings)
+}
+
+fn validate(layout: &dyn Layout, warnings: &mut Vec<LayoutWarning>) -> Result<(), LayoutError> {
+    let parent = Rect::of(layout);
+    check_layout_flex(layout)?;
+    check_size(parent)?;
+
+    for child in layout.children() {
+        validate(child, warnings)?;
+        if in_flow(child) && !layout.clips_children() {
+            check_overflow(parent, Rect::of(child), warnings);
+        }
+    }
+
+    Ok(())
+}
+
+/// The id, position and size of a solved layout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Rect {
+    pub id: GlobalId,
+    pub position: Position<f32>,
+    pub size: Size<f32>,
+}
+
+impl Rect {
+    pub(crate) fn of(layout: &dyn Layout) -> Self {
+        Self {
+            id: layout.id(),
+            position: layout.position(),
+            size: layout.size(),
+        }
+    }
+}
+
+/// Returns an error if the size of a layout is negative or not finite.
+pub(crate) fn check_size(rect: Rect) -> Result<(), LayoutError> {
+    let id = rect.id;
+    for (axis, size) in [
+        (Axis::Horizontal, rect.size.width),
+        (Axis::Vertical, rect.size.height),
+    ] {
+        if !size.is_finite() {
+            return Err(LayoutError::NonFiniteSize { id, axis, size });
+        }
+        if size < 0.0 {
+            return Err(LayoutError::NegativeSize { id, axis, size });
+        }
+    }
+    Ok(())
+}
+
+/// Returns an error if any flex weight is zero, negative or not finite.
+pub(crate) fn check_flex(
+    id: GlobalId,
+    sizing: impl IntoIterator<Item = (Axis, BoxSizing)>,
+) -> Result<(), LayoutError> {
+    for (axis, sizing) in sizing {
+        if let BoxSizing::Flex(flex) = sizing
+            && !(flex > 0.0 && flex.is_finite())
+        {
+            return Err(LayoutError::InvalidFlex { id, axis, flex });
+        }
+    }
+    Ok(())
+}
+
+/// Check the flex weights of a layout, and all of it's children, before
+/// it is solved. Invalid weights can't be shared out, so they have to be
+/// caught before the max constraints are solved.
+pub(crate) fn check_flex_weights(layout: &dyn Layout) -> Result<(), LayoutError> {
+    check_layout_flex(layout)?;
+    for child in layout.children() {
+        check_flex_weights(child)?;
+    }
+    Ok(())
+}
+
+/// Check the flex weights of a layout's size and it's tracks, if it
+/// has any.
+fn check_layout_flex(layout: &dyn Layout) -> Result<(), LayoutError> {
+    let sizing = [
+        (Axis::Horizontal, layout.intrinsic_width()),
+        (Axis::Vertical, layout.intrinsic_height()),
+    ];
+    check_flex(layout.id(), sizing.into_iter().chain(layout.track_sizing()))
+}
+
+/// Add a warning for each axis that a child overflows it's parent.
+pub(crate) fn check_overflow(parent: Rect, child: Rect, warnings: &mut Vec<LayoutWarning>) {
+    for axis in [Axis::Horizontal, Axis::Vertical] {
+        let amount = overflow(parent, child, axis);
+        if amount > OVERFLOW_TOLERANCE {
+            warnings.push(LayoutWarning::Overflow {
+                id: child.id,
+                parent: parent.id,
+                axis,
+                amount,
+            });
+        }
+    }
+}
+
+/// Get the distance that a child extends past either edge of it's
+/// parent along an axis.
+fn overflow(parent: Rect, child: Rect, axis: Axis) -> f32 {
+    let (start, size, child_start, child_size) = match axis {
+        Axis::Horizontal => (
+            parent.position.x,
+            parent.size.width,
+            child.position.x,
+            child.size.width,
+        ),
+        Axis::Vertical => (
+            parent.position.y,
+            parent.size.height,
+            child.position.y,
+            child.size.height,
+        ),
+    };
+
+    let before = start - child_start;
+    let after = (child_start + child_size) - (start + size);
+    before.max(after).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EmptyLayout, HorizontalLayout};
+
+    #[test]
+    fn overflow_past_end() {
+        let mut parent = EmptyLayout::new();
+        parent.set_size(Size::unit(100.0));
+        let mut child = EmptyLayout::new();
+        child.set_position(Position::new(50.0, 10.0));
+        child.set_size(Size::new(80.0, 20.0));
+
+        assert_eq!(
+            overflow(Rect::of(&parent), Rect::of(&child), Axis::Horizontal),
+            30.0
+        );
+        assert_eq!(
+            overflow(Rect::of(&parent), Rect::of(&child), Axis::Vertical),
+            0.0
+        );
+    }
+
+    #[test]
+    fn overflow_before_start() {
+        let mut parent = EmptyLayout::new();
+        parent.set_position(Position::new(20.0, 20.0));
+        parent.set_size(Size::unit(100.0));
+        let mut child = EmptyLayout::new();
+        child.set_position(Position::new(20.0, 5.0));
+        child.set_size(Size::unit(50.0));
+
+        assert_eq!(
+            overflow(Rect::of(&parent), Rect::of(&child), Axis::Vertical),
+            15.0
+        );
+    }
+
+    #[test]
+    fn check_flex_weights() {
+        let id = GlobalId::new();
+        assert!(check_flex(id, [(Axis::Horizontal, BoxSizing::Flex(0.5))]).is_ok());
+        assert_eq!(
+            check_flex(
+                id,
+                [
+                    (Axis::Horizontal, BoxSizing::Fit),
+                    (Axis::Vertical, BoxSizing::Flex(-1.0))
+                ]
+            ),
+            Err(LayoutError::InvalidFlex {
+                id,
+                axis: Axis::Vertical,
+                flex: -1.0
+            })
+        );
+        assert!(check_flex(id, [(Axis::Horizontal, BoxSizing::Flex(f32::INFINITY))]).is_err());
+    }
+
+    #[test]
+    fn negative_size() {
+        let mut layout = HorizontalLayout::new();
+        layout.set_size(Size::new(-20.0, 10.0));
+        let error = validate_layout(&layout).unwrap_err();
+
+        assert_eq!(
+            error,
+            LayoutError::NegativeSize {
+                id: layout.id(),
+                axis: Axis::Horizontal,
+                size: -20.0
+            }
+        );
+    }
+}
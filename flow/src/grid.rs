@@ -0,0 +1,675 @@
+use crate::absolute::{constrain_absolute, in_flow, position_absolute};
+use crate::cache::LayoutCache;
+use crate::content::{ContentSize, fit_height, fit_width, outer_height, outer_width};
+use crate::snap::snap_edges;
+use crate::{
+    Axis, BoxConstraints, BoxSizing, Layout, LayoutDirection, Margin, Padding, Position, Size,
+    SizeBounds, constrain_aspect_ratio, impl_layout, impl_margin, impl_padding, impl_shrink,
+    impl_size,
+};
+use ruby_core::GlobalId;
+use std::collections::HashSet;
+
+/// The cell, or cells, that a child of a [`GridLayout`] occupies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridPlacement {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+
+impl GridPlacement {
+    pub fn new(row: usize, column: usize) -> Self {
+        Self {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    pub fn span(mut self, rows: usize, columns: usize) -> Self {
+        self.row_span = rows.max(1);
+        self.column_span = columns.max(1);
+        self
+    }
+
+    /// Make sure the placement covers at least one cell, since the
+    /// spans can be set to zero directly.
+    fn clamped(self) -> Self {
+        self.span(self.row_span, self.column_span)
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> {
+        let columns = self.column..self.column + self.column_span;
+        (self.row..self.row + self.row_span)
+            .flat_map(move |row| columns.clone().map(move |column| (row, column)))
+    }
+}
+
+/// A [`Layout`] that arranges it's children in rows and columns.
+///
+/// Each track, a row or a column, is sized using a [`BoxSizing`]:
+/// - [`BoxSizing::Fixed`] tracks are always the same size.
+/// - [`BoxSizing::Fit`] tracks are as large as the largest child in them.
+/// - [`BoxSizing::Flex`] tracks divide the remaining space between
+///   them, according to their flex factor.
+///
+/// Children can either be placed in a specific cell, using
+/// [`GridLayout::place`], or automatically placed in the next free cell
+/// using [`GridLayout::push`]. Rows that are not defined are added as
+/// needed and fit their content.
+///
+/// # Example
+/// ```
+/// use flow::{GridLayout,GridPlacement,EmptyLayout,BoxSizing,Size,Layout};
+///
+/// let mut grid = GridLayout::new()
+///     .columns([BoxSizing::Fixed(100.0), BoxSizing::Fixed(200.0)]);
+/// grid.column_gap = 10;
+/// grid.push(EmptyLayout::new().fixed(50.0, 20.0));
+/// grid.push(EmptyLayout::new().fixed(50.0, 40.0));
+/// grid.place(EmptyLayout::new().fixed(50.0, 30.0), GridPlacement::new(1, 1));
+///
+/// flow::solve_layout(&mut grid,Size::unit(1000.0)).unwrap();
+/// assert_eq!(grid.size(),Size::new(310.0, 70.0));
+/// ```
+#[derive(Debug, Default)]
+pub struct GridLayout {
+    id: GlobalId,
+    size: Size<f32>,
+    position: Position<f32>,
+    children: Vec<Box<dyn Layout>>,
+    placements: Vec<Option<GridPlacement>>,
+    pub columns: Vec<BoxSizing>,
+    pub rows: Vec<BoxSizing>,
+    pub column_gap: u32,
+    pub row_gap: u32,
+    pub padding: Padding,
+    pub intrinsic_width: BoxSizing,
+    pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    /// Skip this layout and it's children when hit testing
+    pub non_interactive: bool,
+    pub margin: Margin,
+    /// How much the layout shrinks, relative to it's siblings, when it's
+    /// parent runs out of space. Defaults to zero, so layouts keep their
+    /// size unless they opt in.
+    pub shrink: f32,
+    direction: LayoutDirection,
+    scale_factor: Option<f32>,
+    constraints: BoxConstraints,
+    cache: LayoutCache,
+    column_sizes: Vec<f32>,
+    row_sizes: Vec<f32>,
+}
+
+impl GridLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn columns(mut self, columns: impl IntoIterator<Item = BoxSizing>) -> Self {
+        self.columns = columns.into_iter().collect();
+        self
+    }
+
+    pub fn rows(mut self, rows: impl IntoIterator<Item = BoxSizing>) -> Self {
+        self.rows = rows.into_iter().collect();
+        self
+    }
+
+    pub fn children(&self) -> &[Box<dyn Layout>] {
+        self.children.as_slice()
+    }
+
+    /// Add a child to the next free cell.
+    pub fn push(&mut self, child: impl Layout + 'static) {
+        self.mark_dirty();
+        self.children.push(Box::new(child));
+        self.placements.push(None);
+    }
+
+    /// Insert a child at `index`, it will be placed in the next free cell.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of children.
+    pub fn insert(&mut self, index: usize, child: impl Layout + 'static) {
+        self.mark_dirty();
+        self.children.insert(index, Box::new(child));
+        self.placements.insert(index, None);
+    }
+
+    /// Add a child to a specific cell.
+    pub fn place(&mut self, child: impl Layout + 'static, placement: GridPlacement) {
+        self.mark_dirty();
+        self.children.push(Box::new(child));
+        self.placements.push(Some(placement));
+    }
+
+    /// Get the solved size of each column
+    pub fn column_sizes(&self) -> &[f32] {
+        &self.column_sizes
+    }
+
+    /// Get the solved size of each row
+    pub fn row_sizes(&self) -> &[f32] {
+        &self.row_sizes
+    }
+
+    /// Resolve the cells of every child, placing the children without
+    /// an explicit placement in the first free cell, row by row.
+    /// Absolutely positioned children don't occupy any cells.
+    fn areas(&self) -> Vec<Option<GridPlacement>> {
+        let placements: Vec<_> = self
+            .placements
+            .iter()
+            .map(|p| p.map(GridPlacement::clamped))
+            .collect();
+
+        let column_count = placements
+            .iter()
+            .flatten()
+            .map(|p| p.column + p.column_span)
+            .fold(self.columns.len(), usize::max)
+            .max(1);
+
+        let mut occupied: HashSet<(usize, usize)> = placements
+            .iter()
+            .flatten()
+            .flat_map(|p| p.cells())
+            .collect();
+
+        let mut cursor = 0;
+        let mut areas = vec![];
+        for (child, placement) in self.children.iter().zip(&placements) {
+            if !in_flow(child.as_ref()) {
+                areas.push(None);
+                continue;
+            }
+
+            if let Some(placement) = placement {
+                areas.push(Some(*placement));
+                continue;
+            }
+
+            loop {
+                let area = GridPlacement::new(cursor / column_count, cursor % column_count);
+                cursor += 1;
+                if !occupied.contains(&(area.row, area.column)) {
+                    occupied.insert((area.row, area.column));
+                    areas.push(Some(area));
+                    break;
+                }
+            }
+        }
+
+        areas
+    }
+
+    /// Get the sizing of each track, adding fit tracks for any
+    /// implicit rows or columns.
+    fn tracks(defined: &[BoxSizing], count: usize) -> Vec<BoxSizing> {
+        let mut tracks = defined.to_vec();
+        tracks.resize(count.max(defined.len()), BoxSizing::Fit);
+        tracks
+    }
+
+    /// Solve the size of the rows and columns using the min constraints
+    /// of the children.
+    fn solve_tracks(&mut self) {
+        let (areas, children): (Vec<_>, Vec<_>) = self
+            .areas()
+            .into_iter()
+            .zip(&self.children)
+            .filter_map(|(area, child)| Some((area?, (child.constraints(), child.margin()))))
+            .unzip();
+        let column_count = areas.iter().map(|a| a.column + a.column_span).max();
+        let row_count = areas.iter().map(|a| a.row + a.row_span).max();
+        let columns = Self::tracks(&self.columns, column_count.unwrap_or_default());
+        let rows = Self::tracks(&self.rows, row_count.unwrap_or_default());
+
+        let widths: Vec<_> = children
+            .iter()
+            .map(|(c, margin)| c.min_width + margin.horizontal())
+            .collect();
+        let heights: Vec<_> = children
+            .iter()
+            .map(|(c, margin)| c.min_height + margin.vertical())
+            .collect();
+        let column_spans: Vec<_> = areas.iter().map(|a| (a.column, a.column_span)).collect();
+        let row_spans: Vec<_> = areas.iter().map(|a| (a.row, a.row_span)).collect();
+
+        let padding = self.padding.left + self.padding.right;
+        let available = self.constraints.max_width - padding as f32;
+        self.column_sizes = solve_track_sizes(
+            &columns,
+            &column_spans,
+            &widths,
+            available,
+            self.column_gap as f32,
+            self.intrinsic_width == BoxSizing::Fit,
+        );
+
+        let padding = self.padding.top + self.padding.bottom;
+        let available = self.constraints.max_height - padding as f32;
+        self.row_sizes = solve_track_sizes(
+            &rows,
+            &row_spans,
+            &heights,
+            available,
+            self.row_gap as f32,
+            self.intrinsic_height == BoxSizing::Fit,
+        );
+    }
+
+    /// Set the max constraints of the children to the size of their
+    /// cells.
+    fn constrain_children(&mut self) {
+        let areas = self.areas();
+        for (child, area) in self.children.iter_mut().zip(areas) {
+            // Absolutely positioned children are constrained once
+            // the size of this layout is known
+            let Some(area) = area else {
+                continue;
+            };
+
+            let width = span_size(&self.column_sizes, area.column, area.column_span);
+            let width = width + gaps(self.column_gap, area.column_span);
+            let height = span_size(&self.row_sizes, area.row, area.row_span);
+            let height = height + gaps(self.row_gap, area.row_span);
+            let margin = child.margin();
+
+            match child.intrinsic_width() {
+                BoxSizing::Fit => {
+                    let available = width - margin.horizontal();
+                    child.set_max_width(fit_width(child.as_ref(), available, None));
+                }
+                BoxSizing::Flex(_) => child.set_max_width(width - margin.horizontal()),
+                BoxSizing::Fixed(width) => child.set_max_width(width),
+                BoxSizing::Percent(percent) => {
+                    child.set_max_width(BoxSizing::percent_of(percent, width))
+                }
+            }
+
+            match child.intrinsic_height() {
+                BoxSizing::Fit => {
+                    let available = height - margin.vertical();
+                    let width = Some(child.constraints().max_width);
+                    child.set_max_height(fit_height(child.as_ref(), available, width));
+                }
+                BoxSizing::Flex(_) => child.set_max_height(height - margin.vertical()),
+                BoxSizing::Fixed(height) => child.set_max_height(height),
+                BoxSizing::Percent(percent) => {
+                    child.set_max_height(BoxSizing::percent_of(percent, height))
+                }
+            }
+
+            child.solve_max_constraints();
+        }
+    }
+
+    /// Get the content width of the grid, with each column as wide as
+    /// the children in it.
+    fn measure_width(&self, size: ContentSize, _height: Option<f32>) -> f32 {
+        self.intrinsic_width.or_content(|| {
+            let (spans, sizes): (Vec<_>, Vec<_>) = self
+                .areas()
+                .into_iter()
+                .zip(&self.children)
+                .filter_map(|(area, child)| {
+                    let width = outer_width(child.as_ref(), size, None);
+                    Some(((area?.column, area?.column_span), width))
+                })
+                .unzip();
+            let count = spans.iter().map(|(start, span)| start + span).max();
+            let columns = Self::tracks(&self.columns, count.unwrap_or_default());
+            let gap = self.column_gap as f32;
+            let columns = solve_track_sizes(&columns, &spans, &sizes, 0.0, gap, true);
+
+            let width = columns.iter().sum::<f32>() + gaps(self.column_gap, columns.len());
+            self.bounds.clamp_width(width + self.padding.horizontal())
+        })
+    }
+
+    /// Get the content height of the grid, with each row as tall as the
+    /// children in it.
+    fn measure_height(&self, size: ContentSize, _width: Option<f32>) -> f32 {
+        self.intrinsic_height.or_content(|| {
+            let (spans, sizes): (Vec<_>, Vec<_>) = self
+                .areas()
+                .into_iter()
+                .zip(&self.children)
+                .filter_map(|(area, child)| {
+                    let height = outer_height(child.as_ref(), size, None);
+                    Some(((area?.row, area?.row_span), height))
+                })
+                .unzip();
+            let count = spans.iter().map(|(start, span)| start + span).max();
+            let rows = Self::tracks(&self.rows, count.unwrap_or_default());
+            let gap = self.row_gap as f32;
+            let rows = solve_track_sizes(&rows, &spans, &sizes, 0.0, gap, true);
+
+            let height = rows.iter().sum::<f32>() + gaps(self.row_gap, rows.len());
+            self.bounds.clamp_height(height + self.padding.vertical())
+        })
+    }
+
+    impl_size!();
+    impl_margin!();
+    impl_shrink!();
+    impl_padding!();
+}
+
+/// The total size of the gaps inside an area spanning `span` tracks
+fn gaps(gap: u32, span: usize) -> f32 {
+    (gap as usize * span.saturating_sub(1)) as f32
+}
+
+fn span_size(sizes: &[f32], start: usize, span: usize) -> f32 {
+    sizes.iter().skip(start).take(span).sum()
+}
+
+/// Solve the size of each track along one axis.
+///
+/// Fit tracks grow to fit the children in them, children that span
+/// multiple tracks share any extra space between the fit tracks they
+/// span. Percent tracks are a percentage of the available space. Flex
+/// tracks divide the remaining space, unless `fit_content` is set, in
+/// which case they are sized like fit tracks.
+fn solve_track_sizes(
+    tracks: &[BoxSizing],
+    spans: &[(usize, usize)],
+    min_sizes: &[f32],
+    available: f32,
+    gap: f32,
+    fit_content: bool,
+) -> Vec<f32> {
+    let mut content = vec![0.0f32; tracks.len()];
+    for (&(start, span), &size) in spans.iter().zip(min_sizes) {
+        if span == 1 {
+            content[start] = content[start].max(size);
+        }
+    }
+
+    for (&(start, span), &size) in spans.iter().zip(min_sizes) {
+        if span == 1 {
+            continue;
+        }
+
+        let current: f32 = (start..start + span)
+            .map(|i| match tracks[i] {
+                BoxSizing::Fixed(size) => size,
+                BoxSizing::Percent(percent) => BoxSizing::percent_of(percent, available),
+                _ => content[i],
+            })
+            .sum();
+        let current = current + gap * (span - 1) as f32;
+        let fit_tracks: Vec<usize> = (start..start + span)
+            .filter(|&i| tracks[i] == BoxSizing::Fit)
+            .collect();
+        if size > current && !fit_tracks.is_empty() {
+            let extra = (size - current) / fit_tracks.len() as f32;
+            fit_tracks.iter().for_each(|&i| content[i] += extra);
+        }
+    }
+
+    let mut flex_total = 0.0;
+    let mut used = gap * tracks.len().saturating_sub(1) as f32;
+    for (track, content) in tracks.iter().zip(&content) {
+        match track {
+            BoxSizing::Fixed(size) => used += size,
+            BoxSizing::Percent(percent) => used += BoxSizing::percent_of(*percent, available),
+            BoxSizing::Fit => used += content,
+            BoxSizing::Flex(flex) => flex_total += flex,
+        }
+    }
+    let remaining = (available - used).max(0.0);
+
+    tracks
+        .iter()
+        .zip(content)
+        .map(|(track, content)| match track {
+            BoxSizing::Fixed(size) => *size,
+            BoxSizing::Percent(percent) => BoxSizing::percent_of(*percent, available),
+            BoxSizing::Fit => content,
+            BoxSizing::Flex(_) if fit_content => content,
+            BoxSizing::Flex(flex) => remaining * BoxSizing::flex_factor(*flex, flex_total),
+        })
+        .collect()
+}
+
+impl Layout for GridLayout {
+    fn solve_max_constraints(&mut self) {
+        if self.cache.skip(self.constraints) {
+            return;
+        }
+
+        for child in &mut self.children {
+            child.set_direction(self.direction);
+            child.set_scale_factor(self.scale_factor);
+        }
+
+        constrain_aspect_ratio(self);
+        self.solve_tracks();
+        self.constrain_children();
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if !self.cache.recomputed() {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        for child in &mut self.children {
+            child.solve_min_constraints();
+        }
+
+        // Now that the min constraints of the children are known, the fit
+        // tracks can be solved
+        self.solve_tracks();
+        self.constrain_children();
+
+        let columns = self.column_sizes.len();
+        let padding = self.padding.left + self.padding.right;
+        let min_width =
+            self.column_sizes.iter().sum::<f32>() + gaps(self.column_gap, columns) + padding as f32;
+
+        let rows = self.row_sizes.len();
+        let padding = self.padding.top + self.padding.bottom;
+        let min_height =
+            self.row_sizes.iter().sum::<f32>() + gaps(self.row_gap, rows) + padding as f32;
+
+        match self.intrinsic_width {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_width(min_width),
+            BoxSizing::Fixed(width) => self.set_min_width(width),
+            BoxSizing::Percent(_) => self.set_min_width(self.constraints.max_width),
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_height(min_height),
+            BoxSizing::Fixed(height) => self.set_min_height(height),
+            BoxSizing::Percent(_) => self.set_min_height(self.constraints.max_height),
+        }
+
+        constrain_aspect_ratio(self);
+        (self.constraints.min_width, self.constraints.min_height)
+    }
+
+    fn update_size(&mut self) {
+        if !self.cache.begin_update() {
+            return;
+        }
+
+        match self.intrinsic_width {
+            BoxSizing::Fit => self.size.width = self.constraints.min_width,
+            BoxSizing::Fixed(width) => self.size.width = width,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.width = self.constraints.max_width
+            }
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit => self.size.height = self.constraints.min_height,
+            BoxSizing::Fixed(height) => self.size.height = height,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.height = self.constraints.max_height
+            }
+        }
+
+        for child in &mut self.children {
+            constrain_absolute(child.as_mut(), self.size);
+            child.update_size();
+        }
+    }
+
+    fn position_children(&mut self) {
+        if self.cache.skip_position(self.position) {
+            return;
+        }
+
+        let areas = self.areas();
+        let x = self.position.x + self.padding.left as f32;
+        let y = self.position.y + self.padding.top as f32;
+
+        for (child, area) in self.children.iter_mut().zip(areas) {
+            let Some(area) = area else {
+                position_absolute(child.as_mut(), self.position, self.size);
+                continue;
+            };
+
+            let column_offset = span_size(&self.column_sizes, 0, area.column)
+                + (self.column_gap as usize * area.column) as f32;
+            let row_offset =
+                span_size(&self.row_sizes, 0, area.row) + (self.row_gap as usize * area.row) as f32;
+
+            let margin = child.margin();
+            let child_x = x + column_offset + margin.left as f32;
+            let width = child.size().width;
+            child.set_position(Position {
+                x: self
+                    .direction
+                    .place(child_x, width, self.position.x, self.size.width),
+                y: y + row_offset + margin.top as f32,
+            });
+            child.position_children();
+        }
+    }
+
+    fn snap_to_pixels(&mut self) {
+        snap_edges(self);
+        for child in &mut self.children {
+            child.snap_to_pixels();
+        }
+    }
+
+    fn min_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Min, height)
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Max, height)
+    }
+
+    fn min_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Min, width)
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Max, width)
+    }
+
+    fn track_sizing(&self) -> Vec<(Axis, BoxSizing)> {
+        let columns = self.columns.iter().map(|&c| (Axis::Horizontal, c));
+        let rows = self.rows.iter().map(|&r| (Axis::Vertical, r));
+        columns.chain(rows).collect()
+    }
+
+    fn children(&self) -> Vec<&dyn Layout> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Layout> {
+        self.mark_dirty();
+        self.children
+            .iter_mut()
+            .map(|c| c.as_mut() as &mut dyn Layout)
+            .collect()
+    }
+
+    fn remove_child(&mut self, id: GlobalId) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        self.placements.remove(index);
+        Some(self.children.remove(index))
+    }
+
+    fn replace_child(&mut self, id: GlobalId, child: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        Some(std::mem::replace(&mut self.children[index], child))
+    }
+
+    impl_layout!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EmptyLayout;
+
+    #[test]
+    fn auto_placement() {
+        let mut grid = GridLayout::new().columns([BoxSizing::Fit, BoxSizing::Fit]);
+        grid.push(EmptyLayout::new());
+        grid.push(EmptyLayout::new());
+        grid.push(EmptyLayout::new());
+
+        let areas = grid.areas();
+        assert_eq!(areas[0], Some(GridPlacement::new(0, 0)));
+        assert_eq!(areas[1], Some(GridPlacement::new(0, 1)));
+        assert_eq!(areas[2], Some(GridPlacement::new(1, 0)));
+    }
+
+    #[test]
+    fn auto_placement_skips_occupied_cells() {
+        let mut grid = GridLayout::new().columns([BoxSizing::Fit, BoxSizing::Fit]);
+        grid.push(EmptyLayout::new());
+        grid.place(EmptyLayout::new(), GridPlacement::new(0, 1).span(2, 1));
+        grid.push(EmptyLayout::new());
+        grid.push(EmptyLayout::new());
+
+        let areas = grid.areas();
+        assert_eq!(areas[0], Some(GridPlacement::new(0, 0)));
+        assert_eq!(areas[2], Some(GridPlacement::new(1, 0)));
+        assert_eq!(areas[3], Some(GridPlacement::new(2, 0)));
+    }
+
+    #[test]
+    fn implicit_tracks_fit_content() {
+        let tracks = GridLayout::tracks(&[BoxSizing::Fixed(20.0)], 3);
+        assert_eq!(
+            tracks,
+            [BoxSizing::Fixed(20.0), BoxSizing::Fit, BoxSizing::Fit]
+        );
+    }
+
+    #[test]
+    fn flex_tracks_divide_remaining_space() {
+        let tracks = [
+            BoxSizing::Fixed(100.0),
+            BoxSizing::Flex(1.0),
+            BoxSizing::Flex(3.0),
+        ];
+        let sizes = solve_track_sizes(&tracks, &[], &[], 520.0, 10.0, false);
+        assert_eq!(sizes, [100.0, 100.0, 300.0]);
+    }
+
+    #[test]
+    fn spanning_child_grows_fit_tracks() {
+        let tracks = [BoxSizing::Fit, BoxSizing::Fixed(50.0), BoxSizing::Fit];
+        let spans = [(0, 1), (0, 3)];
+        let sizes = solve_track_sizes(&tracks, &spans, &[20.0, 150.0], 0.0, 0.0, false);
+        assert_eq!(sizes, [60.0, 50.0, 40.0]);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{Layout, Position};
+use ruby_core::GlobalId;
+
+/// Find the layouts under a point, using the solved position and size of
+/// each layout.
+///
+/// Returns the path of ids from the root to the deepest layout that
+/// contains the point, or an empty list if no layout contains it.
+/// Children can be hit outside of their parent, such as absolutely
+/// positioned children, unless the parent clips it's children. When
+/// children overlap, later children are drawn above earlier ones, so
+/// the last child containing the point is the one that gets hit.
+///
+/// # Example
+/// ```
+/// use flow::{EmptyLayout,HorizontalLayout,Layout,Position,Size};
+///
+/// let mut layout = HorizontalLayout::new();
+/// layout.push(EmptyLayout::new().fixed(50.0, 50.0));
+/// layout.push(EmptyLayout::new().fixed(50.0, 50.0));
+/// flow::solve_layout(&mut layout,Size::unit(1000.0)).unwrap();
+///
+/// let path = flow::hit_test(&layout,Position::new(75.0, 20.0));
+/// assert_eq!(path,[layout.id(),layout.children()[1].id()]);
+/// ```
+pub fn hit_test(layout: &dyn Layout, point: Position<f32>) -> Vec<GlobalId> {
+    let mut path = vec![];
+    hit(layout, point, false, &mut path);
+    path
+}
+
+/// Find the layouts under a point, like [`hit_test`], but skip any layouts
+/// that aren't interactive, along with their children.
+pub fn hit_test_interactive(layout: &dyn Layout, point: Position<f32>) -> Vec<GlobalId> {
+    let mut path = vec![];
+    hit(layout, point, true, &mut path);
+    path
+}
+
+/// Add the layout and the topmost child under the point to the path,
+/// returning `false` if neither the layout or any of it's children
+/// were hit.
+fn hit(
+    layout: &dyn Layout,
+    point: Position<f32>,
+    interactive: bool,
+    path: &mut Vec<GlobalId>,
+) -> bool {
+    if interactive && !layout.is_interactive() {
+        return false;
+    }
+
+    let inside = contains(layout, point);
+    if !inside && layout.clips_children() {
+        return false;
+    }
+
+    path.push(layout.id());
+    for child in layout.children().into_iter().rev() {
+        if hit(child, point, interactive, path) {
+            return true;
+        }
+    }
+
+    if !inside {
+        path.pop();
+    }
+    inside
+}
+
+/// Returns `true` if the point is inside the layout. The right and bottom
+/// edges are excluded so that a point on the edge between two siblings
+/// only hits one of them.
+fn contains(layout: &dyn Layout, point: Position<f32>) -> bool {
+    let position = layout.position();
+    let size = layout.size();
+
+    point.x >= position.x
+        && point.y >= position.y
+        && point.x < position.x + size.width
+        && point.y < position.y + size.height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EmptyLayout, Size};
+
+    #[test]
+    fn exclude_right_and_bottom_edge() {
+        let mut layout = EmptyLayout::new();
+        layout.set_position(Position::new(10.0, 10.0));
+        layout.set_size(Size::new(20.0, 20.0));
+
+        assert!(contains(&layout, Position::new(10.0, 10.0)));
+        assert!(contains(&layout, Position::new(29.9, 29.9)));
+        assert!(!contains(&layout, Position::new(30.0, 20.0)));
+        assert!(!contains(&layout, Position::new(20.0, 30.0)));
+    }
+}
@@ -1,11 +1,19 @@
+use crate::absolute::{constrain_absolute, in_flow, position_absolute};
+use crate::cache::LayoutCache;
+use crate::content::{ContentSize, fit_height, outer_height, outer_width, share_fit_space};
+use crate::snap::snap_edges;
 use crate::{
-    BoxConstraints, BoxSizing, Layout, Padding, Position, Size, impl_layout, impl_padding,
-    impl_size,
+    BoxConstraints, BoxSizing, CrossAxisAlignment, Layout, LayoutDirection, MainAxisAlignment,
+    Margin, Padding, Position, Size, SizeBounds, constrain_aspect_ratio, distribute_flex,
+    impl_alignment, impl_layout, impl_margin, impl_padding, impl_shrink, impl_size,
 };
 use ruby_core::GlobalId;
 
 /// A [`Layout`] that arranges it's children horizontally.
 ///
+/// Children that don't fit overflow the layout, unless they opt in to
+/// shrinking with a shrink factor.
+///
 /// # Example
 /// ```
 /// use flow::{HorizontalLayout,EmptyLayout,Size,BoxSizing,Layout};
@@ -20,7 +28,7 @@ use ruby_core::GlobalId;
 /// layout.push(child1);
 /// layout.push(child2);
 ///
-/// flow::solve_layout(&mut layout,Size::unit(1000.0));
+/// flow::solve_layout(&mut layout,Size::unit(1000.0)).unwrap();
 /// assert_eq!(layout.size().width,500.0);
 /// ```
 ///
@@ -32,9 +40,27 @@ pub struct HorizontalLayout {
     children: Vec<Box<dyn Layout>>,
     pub spacing: u32,
     pub padding: Padding,
+    pub main_axis_alignment: MainAxisAlignment,
+    pub cross_axis_alignment: CrossAxisAlignment,
     pub intrinsic_width: BoxSizing,
     pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    /// Skip this layout and it's children when hit testing
+    pub non_interactive: bool,
+    pub margin: Margin,
+    /// How much the layout shrinks, relative to it's siblings, when it's
+    /// parent runs out of space. Defaults to zero, so layouts keep their
+    /// size unless they opt in.
+    pub shrink: f32,
+    direction: LayoutDirection,
+    scale_factor: Option<f32>,
+    /// The width of each child that was shrunk in the last solve
+    shrunk: Vec<Option<f32>>,
+    shrunk_width: f32,
+    overflow_width: f32,
+    baseline: Option<f32>,
     constraints: BoxConstraints,
+    cache: LayoutCache,
 }
 
 impl HorizontalLayout {
@@ -47,9 +73,19 @@ impl HorizontalLayout {
     }
 
     pub fn push(&mut self, child: impl Layout + 'static) {
+        self.mark_dirty();
         self.children.push(Box::new(child));
     }
 
+    /// Insert a child at `index`, shifting all the children after it.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of children.
+    pub fn insert(&mut self, index: usize, child: impl Layout + 'static) {
+        self.mark_dirty();
+        self.children.insert(index, Box::new(child));
+    }
+
     /// Append a list of layouts
     /// 
     /// # Example
@@ -57,7 +93,7 @@ impl HorizontalLayout {
     /// use flow::{HorizontalLayout,EmptyLayout};
     /// 
     /// let mut layout = HorizontalLayout::new();
-    /// layout.append([EmptyLayout::new(),HorizontalLayout::new()]);
+    /// layout.append([EmptyLayout::new(),EmptyLayout::new(),EmptyLayout::new()]);
     /// 
     /// assert_eq!(layout.children().len(),3);
     /// ```
@@ -65,67 +101,266 @@ impl HorizontalLayout {
     where 
         I: IntoIterator<Item: Layout + 'static>, 
     {
+        self.mark_dirty();
         for child in children{
             self.children.push(Box::new(child));
         }
     }
 
+    /// Get the width taken back from the children by shrinking
+    /// them in the last solve.
+    pub fn shrunk_width(&self) -> f32 {
+        self.shrunk_width
+    }
+
+    /// Get the width the children still overflow the layout by,
+    /// after they have been shrunk.
+    pub fn overflow_width(&self) -> f32 {
+        self.overflow_width
+    }
+
+    /// The width available to the children, excluding the padding
+    fn content_width(&self) -> f32 {
+        let padding = self.padding.left + self.padding.right;
+        self.constraints.max_width - padding as f32
+    }
+
     /// Calculate the total width of the children with
-    /// fixed `instrinsic_width`
+    /// fixed or percent `instrinsic_width`
     fn sum_fixed_width(&self) -> f32 {
         let mut sum = 0.0;
-        for child in &self.children {
-            if let BoxSizing::Fixed(width) = child.intrinsic_width() {
-                sum += width
+        for child in self.children.iter().filter(|c| in_flow(c.as_ref())) {
+            match child.intrinsic_width() {
+                BoxSizing::Fixed(width) => sum += width,
+                BoxSizing::Percent(percent) => {
+                    sum += BoxSizing::percent_of(percent, self.content_width())
+                }
+                _ => {}
             }
         }
         sum
     }
 
-    /// Calculate the total flex factor across all the
-    /// children
-    fn flex_total(&self) -> u8 {
-        // TODO handle overflow?
-        let mut flex_total = 0;
-        for child in &self.children {
-            if let BoxSizing::Flex(flex) = child.intrinsic_width() {
-                flex_total += flex
+    /// Get the max width of each child that fits it's content, sharing
+    /// the `space` left by the fixed children between them. The other
+    /// children get a width of zero.
+    fn fit_widths(&self, space: f32) -> Vec<f32> {
+        let sizes: Vec<_> = self
+            .children
+            .iter()
+            .map(|child| {
+                if in_flow(child.as_ref()) && child.intrinsic_width() == BoxSizing::Fit {
+                    let min = child.min_content_width(None);
+                    (min, child.max_content_width(None))
+                } else {
+                    (0.0, 0.0)
+                }
+            })
+            .collect();
+        share_fit_space(space, &sizes)
+    }
+
+    /// Shrink the fixed, percent and fit children when they take up more
+    /// than the `available` width. Children are never shrunk below their
+    /// min width, or their min content width if they fit their content.
+    ///
+    /// Returns the width of the fixed and percent children after shrinking.
+    fn shrink_children(&mut self, available: f32, fit_widths: &[f32]) -> f32 {
+        let content_width = self.content_width();
+        let mut indices = vec![];
+        let mut widths = vec![];
+        let mut mins = vec![];
+        let mut factors = vec![];
+
+        for (i, child) in self.children.iter().enumerate() {
+            if !in_flow(child.as_ref()) {
+                continue;
             }
+
+            let bounds = child.bounds();
+            let (width, min) = match child.intrinsic_width() {
+                BoxSizing::Fixed(width) => (width, bounds.min_width),
+                BoxSizing::Percent(percent) => {
+                    let width = BoxSizing::percent_of(percent, content_width);
+                    (width, bounds.min_width)
+                }
+                BoxSizing::Fit => (fit_widths[i], child.min_content_width(None)),
+                BoxSizing::Flex(_) => continue,
+            };
+            indices.push(i);
+            widths.push(width);
+            mins.push(min.min(width));
+            factors.push(child.shrink());
         }
-        flex_total
+
+        let overflow = widths.iter().sum::<f32>() - available;
+        let sizes = shrink_sizes(&widths, &mins, &factors, overflow);
+
+        self.shrunk = vec![None; self.children.len()];
+        self.shrunk_width = 0.0;
+        let mut fixed_shrunk = 0.0;
+        for ((&i, &width), &size) in indices.iter().zip(&widths).zip(&sizes) {
+            if size >= width {
+                continue;
+            }
+            self.shrunk[i] = Some(size);
+            self.shrunk_width += width - size;
+            if self.children[i].intrinsic_width() != BoxSizing::Fit {
+                fixed_shrunk += width - size;
+            }
+        }
+        self.overflow_width = (overflow - self.shrunk_width).max(0.0);
+        self.sum_fixed_width() - fixed_shrunk
+    }
+
+    /// Get the distance from the top of the content to the baseline that
+    /// the children are aligned to, using their current size.
+    fn max_ascent(&self) -> f32 {
+        self.children
+            .iter()
+            .filter(|c| in_flow(c.as_ref()))
+            .map(|c| ascent_descent(c.as_ref(), c.size().height).0)
+            .fold(0.0, f32::max)
+    }
+
+    /// Calculate the total horizontal margin of the children
+    fn total_margin(&self) -> f32 {
+        self.children
+            .iter()
+            .filter(|c| in_flow(c.as_ref()))
+            .map(|c| c.margin().horizontal())
+            .sum()
+    }
+
+    /// Calculate the total space taken up by the spacing
+    /// between the children
+    fn total_spacing(&self) -> f32 {
+        let count = self.children.iter().filter(|c| in_flow(c.as_ref())).count();
+        let gaps = count.saturating_sub(1);
+        (self.spacing as usize * gaps) as f32
+    }
+
+    /// Get the content width of the layout, with the children side
+    /// by side
+    fn measure_width(&self, size: ContentSize, height: Option<f32>) -> f32 {
+        self.intrinsic_width.or_content(|| {
+            let height = height.map(|height| height - self.padding.vertical());
+            let width: f32 = self
+                .children
+                .iter()
+                .map(|child| outer_width(child.as_ref(), size, height))
+                .sum();
+            let width = width + self.total_spacing() + self.padding.horizontal();
+            self.bounds.clamp_width(width)
+        })
+    }
+
+    /// Get the content height of the layout, which is the height of the
+    /// tallest child
+    fn measure_height(&self, size: ContentSize, _width: Option<f32>) -> f32 {
+        self.intrinsic_height.or_content(|| {
+            let height = self
+                .children
+                .iter()
+                .map(|child| outer_height(child.as_ref(), size, None))
+                .fold(0.0, f32::max);
+            self.bounds.clamp_height(height + self.padding.vertical())
+        })
+    }
+
+    /// Share the flex space between the flex children, in the
+    /// order of the children
+    fn flex_widths(&self, space: f32) -> Vec<f32> {
+        let (weights, bounds): (Vec<_>, Vec<_>) = self
+            .children
+            .iter()
+            .filter(|c| in_flow(c.as_ref()))
+            .filter_map(|c| match c.intrinsic_width() {
+                BoxSizing::Flex(flex) => {
+                    let bounds = c.bounds();
+                    Some((flex, (bounds.min_width, bounds.max_width)))
+                }
+                _ => None,
+            })
+            .unzip();
+        distribute_flex(space, &weights, &bounds)
     }
 
     impl_size!();
+    impl_margin!();
+    impl_shrink!();
     impl_padding!();
+    impl_alignment!();
 }
 
 impl Layout for HorizontalLayout {
     fn solve_max_constraints(&mut self) {
-        let flex_total = self.flex_total();
-        let total_width = self.sum_fixed_width();
-        let max_width = self.constraints.max_width - total_width;
+        if self.cache.skip(self.constraints) {
+            return;
+        }
 
         for child in &mut self.children {
-            match child.intrinsic_width() {
-                BoxSizing::Fit => {
-                    child.set_max_width(child.constraints().min_width);
-                }
-                BoxSizing::Flex(flex) => {
-                    let factor = flex as f32 / flex_total as f32;
-                    let width = max_width * factor;
-                    child.set_max_width(width);
+            child.set_direction(self.direction);
+            child.set_scale_factor(self.scale_factor);
+        }
+
+        constrain_aspect_ratio(self);
+        let content_width = self.content_width();
+        let available = (content_width - self.total_spacing() - self.total_margin()).max(0.0);
+        // The fixed and fit children may take up more than the available
+        // space, in which case they are shrunk and the flex children get
+        // nothing
+        let fit_widths = self.fit_widths((available - self.sum_fixed_width()).max(0.0));
+        let fixed_width = self.shrink_children(available, &fit_widths);
+        let fit_width: f32 = self
+            .children
+            .iter()
+            .zip(self.shrunk.iter().zip(&fit_widths))
+            .filter(|(c, _)| in_flow(c.as_ref()) && c.intrinsic_width() == BoxSizing::Fit)
+            .map(|(_, (shrunk, width))| shrunk.unwrap_or(*width))
+            .sum();
+        let max_width = (available - fixed_width - fit_width).max(0.0);
+        let padding = self.padding.top + self.padding.bottom;
+        let max_height = self.constraints.max_height - padding as f32;
+        let mut flex_sizes = self.flex_widths(max_width).into_iter();
+
+        let children = self.children.iter_mut().zip(&self.shrunk);
+        for ((child, shrunk), fit_width) in children.zip(fit_widths) {
+            // Absolutely positioned children are constrained once
+            // the size of this layout is known
+            if !in_flow(child.as_ref()) {
+                continue;
+            }
+
+            if let Some(width) = shrunk {
+                child.set_max_width(*width);
+            } else {
+                match child.intrinsic_width() {
+                    BoxSizing::Fit => child.set_max_width(fit_width),
+                    BoxSizing::Flex(_) => {
+                        let width = flex_sizes.next().unwrap_or_default();
+                        child.set_max_width(width);
+                    }
+                    BoxSizing::Fixed(width) => child.set_max_width(width),
+                    BoxSizing::Percent(percent) => {
+                        child.set_max_width(BoxSizing::percent_of(percent, content_width));
+                    }
                 }
-                BoxSizing::Fixed(width) => child.set_max_width(width),
             }
 
             match child.intrinsic_height() {
                 BoxSizing::Fit => {
-                    child.set_max_height(child.constraints().min_height);
+                    let width = child.constraints().max_width;
+                    let available = max_height - child.margin().vertical();
+                    child.set_max_height(fit_height(child.as_ref(), available, Some(width)));
                 }
                 BoxSizing::Flex(_) => {
-                    child.set_max_height(self.constraints.max_height);
+                    child.set_max_height(max_height - child.margin().vertical());
                 }
                 BoxSizing::Fixed(height) => child.set_max_height(height),
+                BoxSizing::Percent(percent) => {
+                    child.set_max_height(BoxSizing::percent_of(percent, max_height));
+                }
             }
 
             child.solve_max_constraints();
@@ -133,56 +368,272 @@ impl Layout for HorizontalLayout {
     }
 
     fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if !self.cache.recomputed() {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
         let mut min_width = 0.0;
-        let mut min_height = 0.0;
+        let mut min_height: f32 = 0.0;
+        let mut ascent: f32 = 0.0;
+        let mut descent: f32 = 0.0;
+
+        for (i, child) in self.children.iter_mut().enumerate() {
+            let (width, height) = child.solve_min_constraints();
+            if !in_flow(child.as_ref()) {
+                continue;
+            }
+            let width = self.shrunk.get(i).copied().flatten().unwrap_or(width);
+            let margin = child.margin();
+            min_width += width + margin.horizontal();
+            min_height = min_height.max(height + margin.vertical());
+
+            let (child_ascent, child_descent) = ascent_descent(child.as_ref(), height);
+            ascent = ascent.max(child_ascent);
+            descent = descent.max(child_descent);
+        }
 
-        for child in &mut self.children {
-            let constraints = child.solve_min_constraints();
-            min_width += constraints.0;
-            min_height += constraints.1;
+        // Baseline aligned children stick out above and below each other,
+        // so the layout has to fit the highest ascent and deepest descent
+        self.baseline = None;
+        if self.cross_axis_alignment == CrossAxisAlignment::Baseline {
+            min_height = min_height.max(ascent + descent);
+            self.baseline = Some(self.padding.top as f32 + ascent);
         }
 
-        // TODO add padding
+        let padding = self.padding.left + self.padding.right;
+        min_width += self.total_spacing() + padding as f32;
+        let padding = self.padding.top + self.padding.bottom;
+        min_height += padding as f32;
+
         match self.intrinsic_width {
             BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_width(min_width),
             BoxSizing::Fixed(width) => self.set_min_width(width),
+            BoxSizing::Percent(_) => self.set_min_width(self.constraints.max_width),
         }
 
         match self.intrinsic_height {
             BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_height(min_height),
             BoxSizing::Fixed(height) => self.set_min_height(height),
+            BoxSizing::Percent(_) => self.set_min_height(self.constraints.max_height),
         }
 
-        (min_width, min_height)
+        constrain_aspect_ratio(self);
+        (self.constraints.min_width, self.constraints.min_height)
     }
 
     fn update_size(&mut self) {
+        if !self.cache.begin_update() {
+            return;
+        }
+
         match self.intrinsic_width {
             BoxSizing::Fit => self.size.width = self.constraints.min_width,
             BoxSizing::Fixed(width) => self.size.width = width,
-            BoxSizing::Flex(_) => self.size.width = self.constraints.max_width,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.width = self.constraints.max_width
+            }
         }
 
         match self.intrinsic_height {
             BoxSizing::Fit => self.size.height = self.constraints.min_height,
             BoxSizing::Fixed(height) => self.size.height = height,
-            BoxSizing::Flex(_) => self.size.height = self.constraints.max_height,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.height = self.constraints.max_height
+            }
+        }
+
+        for (i, child) in self.children.iter_mut().enumerate() {
+            constrain_absolute(child.as_mut(), self.size);
+            child.update_size();
+            // Fixed children ignore their max width, so they're resized here
+            if let Some(Some(width)) = self.shrunk.get(i) {
+                child.set_size(Size::new(*width, child.size().height));
+            }
         }
 
-        self.children.iter_mut().for_each(|c| c.update_size());
+        if self.baseline.is_some() {
+            self.baseline = Some(self.padding.top as f32 + self.max_ascent());
+        }
     }
 
     fn position_children(&mut self) {
-        
+        if self.cache.skip_position(self.position) {
+            return;
+        }
+
+        let padding = self.padding.left + self.padding.right;
+        let children = self.children.iter().filter(|c| in_flow(c.as_ref()));
+        let count = children.clone().count();
+        let content: f32 = children
+            .map(|c| c.size().width + c.margin().horizontal())
+            .sum();
+        let free_space = self.size.width - padding as f32 - self.total_spacing() - content;
+        let (offset, gap) = self.main_axis_alignment.distribute(free_space, count);
+
+        let cross_padding = self.padding.top + self.padding.bottom;
+        let cross_space = self.size.height - cross_padding as f32;
+        let max_ascent = self.max_ascent();
+
+        let mut x = self.position.x + self.padding.left as f32 + offset;
+        for child in &mut self.children {
+            if !in_flow(child.as_ref()) {
+                position_absolute(child.as_mut(), self.position, self.size);
+                continue;
+            }
+
+            let margin = child.margin();
+            let free_space = cross_space - child.size().height - margin.vertical();
+            let offset = match self.cross_axis_alignment {
+                CrossAxisAlignment::Baseline => {
+                    max_ascent - ascent_descent(child.as_ref(), child.size().height).0
+                }
+                alignment => alignment.offset(free_space),
+            };
+            let y = self.position.y + self.padding.top as f32 + margin.top as f32 + offset;
+
+            x += margin.left as f32;
+            let width = child.size().width;
+            let child_x = self
+                .direction
+                .place(x, width, self.position.x, self.size.width);
+            child.set_position(Position { x: child_x, y });
+            child.position_children();
+
+            x += width + margin.right as f32;
+            x += self.spacing as f32 + gap;
+        }
+    }
+
+    fn snap_to_pixels(&mut self) {
+        snap_edges(self);
+        for child in &mut self.children {
+            child.snap_to_pixels();
+        }
+    }
+
+    fn min_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Min, height)
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Max, height)
+    }
+
+    fn min_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Min, width)
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Max, width)
+    }
+
+    fn baseline(&self) -> Option<f32> {
+        self.baseline
+    }
+
+    fn children(&self) -> Vec<&dyn Layout> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Layout> {
+        self.mark_dirty();
+        self.children
+            .iter_mut()
+            .map(|c| c.as_mut() as &mut dyn Layout)
+            .collect()
+    }
+
+    fn remove_child(&mut self, id: GlobalId) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        Some(self.children.remove(index))
+    }
+
+    fn replace_child(&mut self, id: GlobalId, child: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        Some(std::mem::replace(&mut self.children[index], child))
     }
 
     impl_layout!();
 }
 
+/// Get the distance from the top of a child's margin to it's baseline, and
+/// from it's baseline to the bottom of it's margin, given it's height.
+/// Children without a baseline are aligned by their bottom edge.
+fn ascent_descent(child: &dyn Layout, height: f32) -> (f32, f32) {
+    let margin = child.margin();
+    let baseline = child.baseline().unwrap_or(height);
+    let ascent = margin.top as f32 + baseline;
+    let descent = height - baseline + margin.bottom as f32;
+    (ascent, descent)
+}
+
+/// Take `overflow` away from the `sizes`, in proportion to their shrink
+/// factor multiplied by their size, without going below their min size.
+/// Sizes that reach their min are frozen and the rest of the overflow is
+/// shared between the others.
+fn shrink_sizes(sizes: &[f32], mins: &[f32], factors: &[f32], overflow: f32) -> Vec<f32> {
+    let mut shrunk = sizes.to_vec();
+    let mut frozen: Vec<bool> = factors
+        .iter()
+        .zip(sizes.iter().zip(mins))
+        .map(|(factor, (size, min))| *factor <= 0.0 || size <= min)
+        .collect();
+
+    loop {
+        let removed: f32 = sizes.iter().zip(&shrunk).map(|(a, b)| a - b).sum();
+        let remaining = overflow - removed;
+        let scaled: f32 = (0..sizes.len())
+            .filter(|&i| !frozen[i])
+            .map(|i| factors[i] * sizes[i])
+            .sum();
+        if remaining <= 0.0 || scaled <= 0.0 {
+            break;
+        }
+
+        let mut clamped = false;
+        for i in 0..sizes.len() {
+            if frozen[i] {
+                continue;
+            }
+            let size = shrunk[i] - remaining * factors[i] * sizes[i] / scaled;
+            if size <= mins[i] {
+                shrunk[i] = mins[i];
+                frozen[i] = true;
+                clamped = true;
+            } else {
+                shrunk[i] = size;
+            }
+        }
+
+        if !clamped {
+            break;
+        }
+    }
+
+    shrunk
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::EmptyLayout;
+    use crate::{EmptyLayout, VerticalLayout, WrapLayout};
+
+    #[test]
+    fn shrink_in_proportion_to_size() {
+        let sizes = shrink_sizes(&[300.0, 100.0], &[0.0, 0.0], &[1.0, 1.0], 100.0);
+        assert_eq!(sizes, [225.0, 75.0]);
+    }
+
+    #[test]
+    fn shrink_stops_at_min() {
+        let sizes = shrink_sizes(&[200.0, 200.0], &[180.0, 0.0], &[1.0, 1.0], 100.0);
+        assert_eq!(sizes, [180.0, 120.0]);
+
+        let sizes = shrink_sizes(&[200.0, 200.0], &[150.0, 0.0], &[0.0, 1.0], 300.0);
+        assert_eq!(sizes, [200.0, 0.0]);
+    }
 
     #[test]
     fn sum_fixed_width() {
@@ -256,14 +707,14 @@ mod tests {
 
     #[test]
     fn fit_max_constraints() {
-        let mut child1 = EmptyLayout::new();
-        let mut child2 = EmptyLayout::new();
-        child1.set_min_width(150.0);
-        child1.set_min_height(50.0);
-        child2.set_min_width(300.0);
-        child2.set_min_height(349.0);
+        let mut child1 = VerticalLayout::new();
+        let mut child2 = VerticalLayout::new();
+        child1.push(EmptyLayout::new().fixed(150.0, 50.0));
+        child2.push(EmptyLayout::new().fixed(300.0, 349.0));
 
         let mut layout = HorizontalLayout::new();
+        layout.set_max_width(1000.0);
+        layout.set_max_height(1000.0);
         layout.push(child1);
         layout.push(child2);
         layout.solve_max_constraints();
@@ -271,22 +722,25 @@ mod tests {
         let child1 = &layout.children[0];
         let child2 = &layout.children[1];
 
-        assert_eq!(
-            child1.constraints().max_width,
-            child1.constraints().min_width
-        );
-        assert_eq!(
-            child2.constraints().max_width,
-            child2.constraints().min_width
-        );
-        assert_eq!(
-            child1.constraints().max_height,
-            child1.constraints().min_height
-        );
-        assert_eq!(
-            child2.constraints().max_height,
-            child2.constraints().min_height
-        );
+        assert_eq!(child1.constraints().max_width, 150.0);
+        assert_eq!(child2.constraints().max_width, 300.0);
+        assert_eq!(child1.constraints().max_height, 50.0);
+        assert_eq!(child2.constraints().max_height, 349.0);
+    }
+
+    #[test]
+    fn fit_children_limited_to_available_space() {
+        let mut child = WrapLayout::new();
+        child.push(EmptyLayout::new().fixed(250.0, 50.0));
+        child.push(EmptyLayout::new().fixed(250.0, 50.0));
+
+        let mut layout = HorizontalLayout::new();
+        layout.set_max_width(400.0);
+        layout.push(EmptyLayout::new().fixed_width(100.0));
+        layout.push(child);
+        layout.solve_max_constraints();
+
+        assert_eq!(layout.children[1].constraints().max_width, 300.0);
     }
 
     #[test]
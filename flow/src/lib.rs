@@ -1,20 +1,60 @@
 //! General purpose layout engine
-//! 
+//!
 //! ## BoxConstraints
 //! [`BoxConstraints`] control the max and min size that a layout is
 //! allowed to be. BoxConstraints are set by the parent and are respected
 //! by the widget when doing the final layout calculation.
 //!
+//! ## Caching
+//! Each layout remembers the max constraints it was last solved with. If
+//! they haven't changed, and the layout hasn't been marked dirty, then
+//! the whole subtree is skipped. Adding children marks a layout dirty,
+//! changing any public fields requires calling [`Layout::mark_dirty`].
+//! Changing nested layouts through [`Layout::find_mut`] or
+//! [`Layout::children_mut`] marks every layout along the way dirty.
+//!
+//! ## Pixel snapping
+//! Flex children often end up with fractional sizes. Setting a scale
+//! factor on the root with [`Layout::set_scale_factor`] rounds the edges
+//! of every layout in the tree to whole device pixels as the last step of
+//! [`solve_layout`], without leaving gaps between layouts that touch.
+//!
+mod absolute;
 mod block;
+mod cache;
+mod content;
+pub mod debug;
 mod empty;
+mod error;
+mod grid;
+mod hit;
 mod horizontal;
-use std::fmt::Debug;
+mod measured;
+mod scroll;
+mod snap;
+mod stack;
+mod tree;
+mod vertical;
+mod wrap;
+use error::check_flex_weights;
+use std::fmt::{Debug, Display};
 
+pub use absolute::{AbsoluteLayout, Insets};
 pub use block::BlockLayout;
+pub use cache::count_recomputed;
 pub use empty::EmptyLayout;
+pub use error::{LayoutError, LayoutWarning, validate_layout};
+pub use grid::{GridLayout, GridPlacement};
+pub use hit::{hit_test, hit_test_interactive};
 pub use horizontal::HorizontalLayout;
+pub use measured::MeasuredLayout;
 use ruby_core::GlobalId;
 pub use ruby_core::{Position, Size};
+pub use scroll::ScrollLayout;
+pub use stack::{Alignment, StackLayout};
+pub use tree::{LayoutTree, Node, NodeId, NodeKind, TreeError};
+pub use vertical::VerticalLayout;
+pub use wrap::WrapLayout;
 
 pub trait Layout: Debug {
     /// Get the layouts id
@@ -45,18 +85,259 @@ pub trait Layout: Debug {
     fn set_min_height(&mut self, height: f32);
 
     fn constraints(&self) -> BoxConstraints;
+    /// Get the size bounds of the layout
+    fn bounds(&self) -> SizeBounds;
+    /// Get the space around the outside of the layout
+    fn margin(&self) -> Margin;
+    /// Get the shrink factor of the layout, which is zero for layouts
+    /// that don't shrink
+    fn shrink(&self) -> f32;
+    /// Get the direction the layout places it's children in
+    fn direction(&self) -> LayoutDirection;
+    /// Set the direction of the layout. The direction is passed down to
+    /// every child when solving, so it only needs to be set on the root.
+    fn set_direction(&mut self, direction: LayoutDirection);
+    /// Get the scale factor that the layout is snapped to pixels at
+    fn scale_factor(&self) -> Option<f32>;
+    /// Round the position and size of the layout to whole device pixels at
+    /// the given scale factor, or `None` to keep the exact values. Like the
+    /// direction, this is passed down to every child when solving.
+    fn set_scale_factor(&mut self, scale_factor: Option<f32>);
+
+    /// Get the narrowest width the layout can be without it's content
+    /// overflowing, given the height if it's known.
+    fn min_content_width(&self, height: Option<f32>) -> f32;
+    /// Get the width the layout would be if it had unlimited space, given
+    /// the height if it's known.
+    fn max_content_width(&self, height: Option<f32>) -> f32;
+    /// Get the shortest height the layout can be without it's content
+    /// overflowing, given the width if it's known.
+    fn min_content_height(&self, width: Option<f32>) -> f32;
+    /// Get the height the layout would be if it had unlimited space, given
+    /// the width if it's known.
+    fn max_content_height(&self, width: Option<f32>) -> f32;
+
+    /// Get the name of the layout's type, used when debugging.
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Get the sizing of the tracks that the layout divides it's space
+    /// into, such as the columns and rows of a grid, so that their flex
+    /// weights can be validated.
+    fn track_sizing(&self) -> Vec<(Axis, BoxSizing)> {
+        vec![]
+    }
+
+    /// Returns `false` if the layout should be skipped by
+    /// [`hit_test_interactive`].
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    /// Get the distance from the top of the layout to the baseline of it's
+    /// first line of content, or `None` if it doesn't have one. This is
+    /// known once the min constraints are solved, and updated once the
+    /// layout is sized.
+    fn baseline(&self) -> Option<f32> {
+        None
+    }
+
+    /// Returns `true` if children outside of the layout are hidden, in
+    /// which case they aren't reported as overflowing.
+    fn clips_children(&self) -> bool {
+        false
+    }
+
+    /// Get the scroll offset, or `None` if the layout doesn't scroll.
+    fn scroll_offset(&self) -> Option<f32> {
+        None
+    }
+
+    /// Set the scroll offset, this does nothing if the layout doesn't
+    /// scroll. The offset is clamped the next time the layout is solved.
+    fn set_scroll_offset(&mut self, _offset: f32) {}
+
+    /// Get the largest scroll offset, or `None` if the layout doesn't
+    /// scroll.
+    fn max_scroll_offset(&self) -> Option<f32> {
+        None
+    }
+
+    /// Get the insets of an absolutely positioned layout, or `None`
+    /// if the layout is part of the normal flow of it's parent.
+    fn absolute_insets(&self) -> Option<Insets> {
+        None
+    }
+
+    /// Get the child layouts
+    fn children(&self) -> Vec<&dyn Layout> {
+        vec![]
+    }
+
+    /// Get mutable references to the child layouts. This marks the
+    /// layout as dirty, since the children may be changed.
+    fn children_mut(&mut self) -> Vec<&mut dyn Layout> {
+        vec![]
+    }
+
+    /// Remove a direct child with the given id, returning it if found.
+    fn remove_child(&mut self, _id: GlobalId) -> Option<Box<dyn Layout>> {
+        None
+    }
+
+    /// Replace a direct child with the given id, returning the old child
+    /// if found.
+    fn replace_child(&mut self, _id: GlobalId, _child: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        None
+    }
+
+    /// Find a layout with the given id anywhere below this layout.
+    ///
+    /// # Example
+    /// ```
+    /// use flow::{EmptyLayout,HorizontalLayout,Layout,VerticalLayout};
+    ///
+    /// let child = EmptyLayout::new();
+    /// let id = child.id();
+    /// let mut row = HorizontalLayout::new();
+    /// row.push(child);
+    /// let mut layout = VerticalLayout::new();
+    /// layout.push(row);
+    ///
+    /// assert_eq!(layout.find(id).unwrap().id(), id);
+    /// ```
+    fn find(&self, id: GlobalId) -> Option<&dyn Layout> {
+        for child in self.children() {
+            if child.id() == id {
+                return Some(child);
+            }
+            if let Some(layout) = child.find(id) {
+                return Some(layout);
+            }
+        }
+        None
+    }
+
+    /// Find a layout with the given id anywhere below this layout. Every
+    /// layout between this one and the found layout is marked as dirty.
+    fn find_mut(&mut self, id: GlobalId) -> Option<&mut dyn Layout> {
+        let index = child_index(self, id)?;
+        let child = self.children_mut().into_iter().nth(index)?;
+        if child.id() == id {
+            return Some(child);
+        }
+        child.find_mut(id)
+    }
+
+    /// Remove a layout with the given id from anywhere below this layout.
+    fn remove(&mut self, id: GlobalId) -> Option<Box<dyn Layout>> {
+        if let Some(child) = self.remove_child(id) {
+            return Some(child);
+        }
+
+        let index = child_index(self, id)?;
+        self.children_mut().into_iter().nth(index)?.remove(id)
+    }
+
+    /// Replace a layout with the given id anywhere below this layout,
+    /// returning the old layout. Nothing is changed if there is no
+    /// layout with the id.
+    fn replace(&mut self, id: GlobalId, layout: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        let index = child_index(self, id)?;
+        if self.children()[index].id() == id {
+            return self.replace_child(id, layout);
+        }
+        self.children_mut()
+            .into_iter()
+            .nth(index)?
+            .replace(id, layout)
+    }
+
+    /// Mark the layout as changed, so that it is solved again by the
+    /// next [`solve_layout`]. This is needed after changing any of the
+    /// public fields of a layout that has already been solved.
+    fn mark_dirty(&mut self);
+    /// Returns `true` if the layout has changed since it was last solved
+    fn is_dirty(&self) -> bool;
+    /// Returns `true` if the layout was solved during the last
+    /// [`solve_layout`], rather than reused from the cache
+    fn was_recomputed(&self) -> bool;
+
     fn solve_max_constraints(&mut self);
     /// Calculate the minimum constraints and pass it back to the parent
     fn solve_min_constraints(&mut self) -> (f32, f32);
 
-
     /// Update the size of the layout after the contraints have been
     /// solved, and any child layouts
     fn update_size(&mut self);
+    /// Set the absolute position of the child layouts, based on this
+    /// layout's position, and recursively position their children
     fn position_children(&mut self);
+
+    /// Round the edges of the layout to whole device pixels, then snap
+    /// the children. This is the last pass of [`solve_layout`], so every
+    /// edge is rounded from it's exact position and edges that touch are
+    /// rounded to the same pixel. Layouts with children must override
+    /// this to snap them after themselves.
+    fn snap_to_pixels(&mut self) {
+        snap::snap_edges(self);
+    }
+}
+
+/// Get the index of the child that is, or contains, the layout with the
+/// given id.
+fn child_index(layout: &(impl Layout + ?Sized), id: GlobalId) -> Option<usize> {
+    layout
+        .children()
+        .into_iter()
+        .position(|child| child.id() == id || child.find(id).is_some())
+}
+
+/// Visit a layout and all of it's children depth first, in the order
+/// they appear in. The visitor is called with each layout and it's depth,
+/// where the root has a depth of 0.
+///
+/// # Example
+/// ```
+/// use flow::{EmptyLayout,HorizontalLayout,VerticalLayout};
+///
+/// let mut row = HorizontalLayout::new();
+/// row.push(EmptyLayout::new());
+/// let mut layout = VerticalLayout::new();
+/// layout.push(row);
+/// layout.push(EmptyLayout::new());
+///
+/// let mut depths = vec![];
+/// flow::visit(&layout, &mut |_, depth| depths.push(depth));
+/// assert_eq!(depths, [0, 1, 2, 1]);
+/// ```
+pub fn visit<F>(layout: &dyn Layout, visitor: &mut F)
+where
+    F: FnMut(&dyn Layout, usize),
+{
+    fn walk<F>(layout: &dyn Layout, depth: usize, visitor: &mut F)
+    where
+        F: FnMut(&dyn Layout, usize),
+    {
+        visitor(layout, depth);
+        for child in layout.children() {
+            walk(child, depth + 1, visitor);
+        }
+    }
+
+    walk(layout, 0, visitor);
 }
 
-pub fn solve_layout(layout: &mut impl Layout, max_size: Size<f32>) {
+/// Solve the size and position of a layout and all of it's children.
+///
+/// Returns any [`LayoutWarning`]s, such as children overflowing their
+/// parents, or a [`LayoutError`] if the layout could not be solved. See
+/// [`validate_layout`].
+pub fn solve_layout(
+    layout: &mut impl Layout,
+    max_size: Size<f32>,
+) -> Result<Vec<LayoutWarning>, LayoutError> {
     // Solve the max BoxConstraints for the root layout
     match layout.intrinsic_width() {
         BoxSizing::Fit | BoxSizing::Flex(_) => {
@@ -65,6 +346,9 @@ pub fn solve_layout(layout: &mut impl Layout, max_size: Size<f32>) {
         BoxSizing::Fixed(width) => {
             layout.set_max_width(width);
         }
+        BoxSizing::Percent(percent) => {
+            layout.set_max_width(BoxSizing::percent_of(percent, max_size.width));
+        }
     }
 
     match layout.intrinsic_height() {
@@ -74,12 +358,69 @@ pub fn solve_layout(layout: &mut impl Layout, max_size: Size<f32>) {
         BoxSizing::Fixed(height) => {
             layout.set_max_height(height);
         }
+        BoxSizing::Percent(percent) => {
+            layout.set_max_height(BoxSizing::percent_of(percent, max_size.height));
+        }
     }
 
+    check_flex_weights(layout)?;
+
     // FIXME set the max size to the root BoxConstraints
     layout.solve_max_constraints();
     layout.solve_min_constraints();
     layout.update_size();
+    layout.position_children();
+    layout.snap_to_pixels();
+    validate_layout(layout)
+}
+
+/// An axis of a layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    /// Get the axis perpendicular to this one
+    pub fn cross(&self) -> Axis {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+        }
+    }
+}
+
+impl Display for Axis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Horizontal => write!(f, "horizontal"),
+            Self::Vertical => write!(f, "vertical"),
+        }
+    }
+}
+
+/// The direction that children are placed in along the horizontal axis.
+///
+/// Under [`LayoutDirection::RightToLeft`] every layout is the mirror
+/// image of it's left to right layout, so the left padding, margins and
+/// insets become the start, on the right hand side.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LayoutDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    /// Get the x position of a child placed at `x` when laying out left to
+    /// right, mirrored inside of it's parent when laying out right to left.
+    pub(crate) fn place(self, x: f32, width: f32, parent_x: f32, parent_width: f32) -> f32 {
+        match self {
+            Self::LeftToRight => x,
+            Self::RightToLeft => parent_x + parent_width - (x - parent_x) - width,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -117,8 +458,23 @@ impl Padding {
             bottom: value,
         }
     }
+
+    /// Get the sum of the left and right sides
+    pub fn horizontal(&self) -> f32 {
+        (self.left + self.right) as f32
+    }
+
+    /// Get the sum of the top and bottom sides
+    pub fn vertical(&self) -> f32 {
+        (self.top + self.bottom) as f32
+    }
 }
 
+/// The space around the outside of a layout, which is included when the
+/// parent sizes and positions it. Margins of absolutely positioned
+/// layouts are ignored.
+pub type Margin = Padding;
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct BoxConstraints {
     pub max_width: f32,
@@ -133,26 +489,238 @@ impl BoxConstraints {
     }
 }
 
+/// Limits on the size of a layout, regardless of it's [`BoxSizing`].
+///
+/// The bounds are applied whenever the constraints of a layout are set,
+/// so they are respected in both the max and min passes. [`BoxSizing::Fixed`]
+/// sizes are not clamped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeBounds {
+    pub min_width: f32,
+    pub max_width: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+    /// The ratio of the width to the height. The height is derived from
+    /// the width, unless only the height is fixed.
+    pub aspect_ratio: Option<f32>,
+}
+
+impl Default for SizeBounds {
+    fn default() -> Self {
+        Self {
+            min_width: 0.0,
+            max_width: f32::INFINITY,
+            min_height: 0.0,
+            max_height: f32::INFINITY,
+            aspect_ratio: None,
+        }
+    }
+}
+
+impl SizeBounds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clamp a width to the bounds, the min width takes priority
+    /// if the bounds overlap.
+    pub fn clamp_width(&self, width: f32) -> f32 {
+        width.min(self.max_width).max(self.min_width)
+    }
+
+    /// Clamp a height to the bounds, the min height takes priority
+    /// if the bounds overlap.
+    pub fn clamp_height(&self, height: f32) -> f32 {
+        height.min(self.max_height).max(self.min_height)
+    }
+}
+
+/// Derive the constraints of one axis from the other using the aspect
+/// ratio of the layout.
+///
+/// Called at the start of the max pass and at the end of the min pass,
+/// once the driving axis is known.
+pub(crate) fn constrain_aspect_ratio(layout: &mut dyn Layout) {
+    let Some(ratio) = layout.bounds().aspect_ratio else {
+        return;
+    };
+    if !ratio.is_finite() || ratio <= 0.0 {
+        return;
+    }
+
+    let constraints = layout.constraints();
+    match (layout.intrinsic_width(), layout.intrinsic_height()) {
+        (BoxSizing::Fixed(_), BoxSizing::Fixed(_)) => {}
+        (_, BoxSizing::Fixed(height)) => {
+            layout.set_min_width(height * ratio);
+            layout.set_max_width(height * ratio);
+        }
+        (width, _) => {
+            let width = match width {
+                BoxSizing::Fixed(width) => width,
+                BoxSizing::Flex(_) | BoxSizing::Percent(_) => constraints.max_width,
+                BoxSizing::Fit => constraints.min_width,
+            };
+            layout.set_min_height(width / ratio);
+            layout.set_max_height(width / ratio);
+        }
+    }
+}
+
+/// Share the flex `space` between flex children in proportion to their
+/// `weights`, keeping each share within the child's `(min, max)` bounds.
+///
+/// Children that are clamped by their bounds are frozen and the space
+/// they give up, or take, is shared between the others. When the clamps
+/// take space in total the children held at their min are frozen first,
+/// otherwise the ones held at their max are, the same way as CSS flexbox.
+pub(crate) fn distribute_flex(space: f32, weights: &[f32], bounds: &[(f32, f32)]) -> Vec<f32> {
+    let mut sizes = vec![0.0; weights.len()];
+    let mut targets = vec![0.0; weights.len()];
+    let mut frozen = vec![false; weights.len()];
+
+    while frozen.contains(&false) {
+        let used: f32 = (0..sizes.len())
+            .filter(|&i| frozen[i])
+            .map(|i| sizes[i])
+            .sum();
+        let remaining = (space - used).max(0.0);
+        let total: f32 = (0..weights.len())
+            .filter(|&i| !frozen[i])
+            .map(|i| weights[i])
+            .sum();
+
+        let mut violation = 0.0;
+        for i in 0..sizes.len() {
+            if frozen[i] {
+                continue;
+            }
+            let (min, max) = bounds[i];
+            targets[i] = remaining * BoxSizing::flex_factor(weights[i], total);
+            sizes[i] = targets[i].min(max).max(min);
+            violation += sizes[i] - targets[i];
+        }
+
+        // Weights that aren't finite give NaN targets, which can't be
+        // resolved, so stop instead of looping forever
+        if violation == 0.0 || violation.is_nan() {
+            break;
+        }
+        let mut froze = false;
+        for i in 0..sizes.len() {
+            let clamped = if violation > 0.0 {
+                sizes[i] > targets[i]
+            } else {
+                sizes[i] < targets[i]
+            };
+            froze |= clamped && !frozen[i];
+            frozen[i] |= clamped;
+        }
+        if !froze {
+            break;
+        }
+    }
+
+    sizes
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
 pub enum BoxSizing {
-    Flex(u8),
+    /// Share the free space in the parent with the other flex siblings,
+    /// in proportion to the weight. Weights must be positive and finite.
+    Flex(f32),
     #[default]
     Fit,
     Fixed(f32),
+    /// A percentage of the space available in the parent, regardless of
+    /// any siblings. Percent sizes are treated as fixed sizes when the
+    /// parent divides it's flex space.
+    Percent(f32),
+}
+
+impl BoxSizing {
+    /// Resolve a percent size against the available space.
+    pub(crate) fn percent_of(percent: f32, available: f32) -> f32 {
+        available * percent / 100.0
+    }
+
+    /// Get the share of the flex space that a flex weight gets, which is
+    /// nothing if the total weight isn't positive.
+    pub(crate) fn flex_factor(flex: f32, total: f32) -> f32 {
+        if total > 0.0 { flex / total } else { 0.0 }
+    }
+
+    /// Resolve an intrinsic size query, fixed sizes are used as is and
+    /// every other size is the size of the content.
+    pub(crate) fn or_content(self, content: impl FnOnce() -> f32) -> f32 {
+        match self {
+            BoxSizing::Fixed(size) => size,
+            _ => content(),
+        }
+    }
 }
 
+/// How children are distributed along the main axis of a layout, similar
+/// to `justify-content` in CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum MainAxisAlignment {
+    #[default]
     Start,
     Center,
     End,
+    /// Distribute the free space evenly between the children, including
+    /// before the first and after the last child.
     SpaceEvenly,
+    /// Distribute the free space evenly between the children, with the
+    /// first and last child placed at the edges.
     SpaceBetween,
 }
 
+impl MainAxisAlignment {
+    /// Returns the offset of the first child and the extra space
+    /// to add between each child, given the free space along the main
+    /// axis and the number of children.
+    pub(crate) fn distribute(&self, free_space: f32, count: usize) -> (f32, f32) {
+        match self {
+            Self::Start => (0.0, 0.0),
+            Self::Center => (free_space / 2.0, 0.0),
+            Self::End => (free_space, 0.0),
+            Self::SpaceEvenly => {
+                let gap = free_space.max(0.0) / (count + 1) as f32;
+                (gap, gap)
+            }
+            Self::SpaceBetween if count > 1 => {
+                let gap = free_space.max(0.0) / (count - 1) as f32;
+                (0.0, gap)
+            }
+            Self::SpaceBetween => (0.0, 0.0),
+        }
+    }
+}
+
+/// How children are placed along the cross axis of a layout, similar
+/// to `align-items` in CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum CrossAxisAlignment {
+    #[default]
     Start,
     Center,
     End,
+    /// Line up the baselines of the children. Only [`HorizontalLayout`]
+    /// aligns by baseline, other layouts treat this the same as `Start`.
+    Baseline,
+}
+
+impl CrossAxisAlignment {
+    /// Returns the offset of a child given the free space along the
+    /// cross axis.
+    pub(crate) fn offset(&self, free_space: f32) -> f32 {
+        match self {
+            Self::Start | Self::Baseline => 0.0,
+            Self::Center => free_space / 2.0,
+            Self::End => free_space,
+        }
+    }
 }
 
 #[macro_export]
@@ -175,18 +743,76 @@ macro_rules! impl_size {
         }
 
         pub fn fill(mut self) -> Self {
-            self.intrinsic_width = $crate::BoxSizing::Flex(1);
-            self.intrinsic_height = $crate::BoxSizing::Flex(1);
+            self.intrinsic_width = $crate::BoxSizing::Flex(1.0);
+            self.intrinsic_height = $crate::BoxSizing::Flex(1.0);
             self
         }
 
         pub fn fill_width(mut self) -> Self {
-            self.intrinsic_width = $crate::BoxSizing::Flex(1);
+            self.intrinsic_width = $crate::BoxSizing::Flex(1.0);
             self
         }
 
         pub fn fill_height(mut self) -> Self {
-            self.intrinsic_height = $crate::BoxSizing::Flex(1);
+            self.intrinsic_height = $crate::BoxSizing::Flex(1.0);
+            self
+        }
+
+        /// Share the free space in the parent along both axes, in
+        /// proportion to the `weight`
+        pub fn flex(mut self, weight: f32) -> Self {
+            self.intrinsic_width = $crate::BoxSizing::Flex(weight);
+            self.intrinsic_height = $crate::BoxSizing::Flex(weight);
+            self
+        }
+
+        /// Share the free width in the parent, in proportion to the `weight`
+        pub fn flex_width(mut self, weight: f32) -> Self {
+            self.intrinsic_width = $crate::BoxSizing::Flex(weight);
+            self
+        }
+
+        /// Share the free height in the parent, in proportion to the `weight`
+        pub fn flex_height(mut self, weight: f32) -> Self {
+            self.intrinsic_height = $crate::BoxSizing::Flex(weight);
+            self
+        }
+
+        /// Set the width to a percentage of the parent's width
+        pub fn width_percent(mut self, percent: f32) -> Self {
+            self.intrinsic_width = $crate::BoxSizing::Percent(percent);
+            self
+        }
+
+        /// Set the height to a percentage of the parent's height
+        pub fn height_percent(mut self, percent: f32) -> Self {
+            self.intrinsic_height = $crate::BoxSizing::Percent(percent);
+            self
+        }
+
+        pub fn min_width(mut self, width: f32) -> Self {
+            self.bounds.min_width = width;
+            self
+        }
+
+        pub fn max_width(mut self, width: f32) -> Self {
+            self.bounds.max_width = width;
+            self
+        }
+
+        pub fn min_height(mut self, height: f32) -> Self {
+            self.bounds.min_height = height;
+            self
+        }
+
+        pub fn max_height(mut self, height: f32) -> Self {
+            self.bounds.max_height = height;
+            self
+        }
+
+        /// Keep the ratio of the width to the height
+        pub fn aspect_ratio(mut self, ratio: f32) -> Self {
+            self.bounds.aspect_ratio = Some(ratio);
             self
         }
     };
@@ -217,6 +843,68 @@ macro_rules! impl_padding {
     };
 }
 
+#[macro_export]
+macro_rules! impl_margin {
+    () => {
+        pub fn margin(mut self, margin: $crate::Margin) -> Self {
+            self.margin = margin;
+            self
+        }
+
+        pub fn margin_left(mut self, margin: u32) -> Self {
+            self.margin.left = margin;
+            self
+        }
+
+        pub fn margin_right(mut self, margin: u32) -> Self {
+            self.margin.right = margin;
+            self
+        }
+
+        pub fn margin_top(mut self, margin: u32) -> Self {
+            self.margin.top = margin;
+            self
+        }
+
+        pub fn margin_bottom(mut self, margin: u32) -> Self {
+            self.margin.bottom = margin;
+            self
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_shrink {
+    () => {
+        /// Let the layout shrink below it's size when it's parent runs out
+        /// of space. Layouts with a larger factor give up more space, but
+        /// never shrink below their min size.
+        ///
+        /// Unlike CSS `flex-shrink`, shrinking is opt-in and the factor is
+        /// zero by default. Fixed sizes are kept exactly, and children that
+        /// don't fit are reported as overflowing instead.
+        pub fn shrink(mut self, factor: f32) -> Self {
+            self.shrink = factor;
+            self
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_alignment {
+    () => {
+        pub fn main_axis_alignment(mut self, alignment: $crate::MainAxisAlignment) -> Self {
+            self.main_axis_alignment = alignment;
+            self
+        }
+
+        pub fn cross_axis_alignment(mut self, alignment: $crate::CrossAxisAlignment) -> Self {
+            self.cross_axis_alignment = alignment;
+            self
+        }
+    };
+}
+
 /// Implement the common layout methods
 #[macro_export]
 macro_rules! impl_layout {
@@ -250,24 +938,74 @@ macro_rules! impl_layout {
         }
 
         fn set_max_height(&mut self, height: f32) {
-            self.constraints.max_height = height;
+            self.constraints.max_height = self.bounds.clamp_height(height);
         }
 
         fn set_max_width(&mut self, width: f32) {
-            self.constraints.max_width = width;
+            self.constraints.max_width = self.bounds.clamp_width(width);
         }
 
         fn set_min_height(&mut self, height: f32) {
-            self.constraints.min_height = height;
+            self.constraints.min_height = self.bounds.clamp_height(height);
         }
 
         fn set_min_width(&mut self, width: f32) {
-            self.constraints.min_width = width;
+            self.constraints.min_width = self.bounds.clamp_width(width);
         }
 
         fn constraints(&self) -> $crate::BoxConstraints {
             self.constraints
         }
+
+        fn bounds(&self) -> $crate::SizeBounds {
+            self.bounds
+        }
+
+        fn margin(&self) -> $crate::Margin {
+            self.margin
+        }
+
+        fn shrink(&self) -> f32 {
+            self.shrink
+        }
+
+        fn direction(&self) -> $crate::LayoutDirection {
+            self.direction
+        }
+
+        fn set_direction(&mut self, direction: $crate::LayoutDirection) {
+            if self.direction != direction {
+                self.direction = direction;
+                self.cache.mark_dirty();
+            }
+        }
+
+        fn scale_factor(&self) -> Option<f32> {
+            self.scale_factor
+        }
+
+        fn set_scale_factor(&mut self, scale_factor: Option<f32>) {
+            if self.scale_factor != scale_factor {
+                self.scale_factor = scale_factor;
+                self.cache.mark_dirty();
+            }
+        }
+
+        fn is_interactive(&self) -> bool {
+            !self.non_interactive
+        }
+
+        fn mark_dirty(&mut self) {
+            self.cache.mark_dirty();
+        }
+
+        fn is_dirty(&self) -> bool {
+            self.cache.is_dirty()
+        }
+
+        fn was_recomputed(&self) -> bool {
+            self.cache.recomputed()
+        }
     };
 }
 
@@ -275,8 +1013,110 @@ macro_rules! impl_layout {
 mod tests {
     use super::*;
 
+    #[test]
+    fn mirror_right_to_left() {
+        let direction = LayoutDirection::RightToLeft;
+        assert_eq!(direction.place(30.0, 20.0, 10.0, 100.0), 70.0);
+        assert_eq!(
+            LayoutDirection::LeftToRight.place(30.0, 20.0, 10.0, 100.0),
+            30.0
+        );
+    }
+
+    #[test]
+    fn flex_redistributes_clamped_space() {
+        let bounds = [(0.0, 300.0), (0.0, f32::INFINITY)];
+        assert_eq!(
+            distribute_flex(1000.0, &[1.0, 1.0], &bounds),
+            [300.0, 700.0]
+        );
+
+        let bounds = [(800.0, f32::INFINITY), (0.0, f32::INFINITY)];
+        assert_eq!(
+            distribute_flex(1000.0, &[1.0, 1.0], &bounds),
+            [800.0, 200.0]
+        );
+    }
+
+    #[test]
+    fn flex_stops_on_invalid_weights() {
+        let bounds = [(0.0, 100.0), (0.0, f32::INFINITY)];
+        let sizes = distribute_flex(500.0, &[f32::INFINITY, 1.0], &bounds);
+        assert_eq!(sizes.len(), 2);
+    }
+
+    #[test]
+    fn flex_min_beats_available_space() {
+        let bounds = [(800.0, f32::INFINITY), (0.0, f32::INFINITY)];
+        assert_eq!(distribute_flex(500.0, &[1.0, 1.0], &bounds), [800.0, 0.0]);
+    }
+
     #[test]
     fn default_box_sizing() {
         assert_eq!(BoxSizing::default(), BoxSizing::Fit);
     }
+
+    #[test]
+    fn clamp_to_bounds() {
+        let bounds = SizeBounds {
+            min_width: 20.0,
+            max_width: 100.0,
+            ..SizeBounds::default()
+        };
+        assert_eq!(bounds.clamp_width(150.0), 100.0);
+        assert_eq!(bounds.clamp_width(10.0), 20.0);
+        assert_eq!(bounds.clamp_height(500.0), 500.0);
+    }
+
+    #[test]
+    fn min_bound_takes_priority() {
+        let bounds = SizeBounds {
+            min_height: 80.0,
+            max_height: 40.0,
+            ..SizeBounds::default()
+        };
+        assert_eq!(bounds.clamp_height(60.0), 80.0);
+    }
+
+    #[test]
+    fn distribute_main_axis() {
+        assert_eq!(MainAxisAlignment::Start.distribute(120.0, 3), (0.0, 0.0));
+        assert_eq!(MainAxisAlignment::Center.distribute(120.0, 3), (60.0, 0.0));
+        assert_eq!(MainAxisAlignment::End.distribute(120.0, 3), (120.0, 0.0));
+        assert_eq!(
+            MainAxisAlignment::SpaceEvenly.distribute(120.0, 3),
+            (30.0, 30.0)
+        );
+        assert_eq!(
+            MainAxisAlignment::SpaceBetween.distribute(120.0, 3),
+            (0.0, 60.0)
+        );
+    }
+
+    #[test]
+    fn space_alignment_ignores_negative_space() {
+        assert_eq!(
+            MainAxisAlignment::SpaceEvenly.distribute(-50.0, 2),
+            (0.0, 0.0)
+        );
+        assert_eq!(
+            MainAxisAlignment::SpaceBetween.distribute(-50.0, 2),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn space_between_single_child() {
+        assert_eq!(
+            MainAxisAlignment::SpaceBetween.distribute(100.0, 1),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn cross_axis_offset() {
+        assert_eq!(CrossAxisAlignment::Start.offset(50.0), 0.0);
+        assert_eq!(CrossAxisAlignment::Center.offset(50.0), 25.0);
+        assert_eq!(CrossAxisAlignment::End.offset(50.0), 50.0);
+    }
 }
@@ -0,0 +1,262 @@
+use crate::cache::LayoutCache;
+use crate::{
+    BoxConstraints, BoxSizing, Layout, LayoutDirection, Margin, Position, Size, SizeBounds,
+    constrain_aspect_ratio, impl_layout, impl_margin, impl_shrink, impl_size,
+};
+use ruby_core::GlobalId;
+use std::fmt::Debug;
+
+type Measure = Box<dyn Fn(BoxConstraints) -> Size<f32>>;
+type MeasureBaseline = Box<dyn Fn(Size<f32>) -> f32>;
+
+/// A leaf [`Layout`] that measures it's content, such as text or
+/// images.
+///
+/// The measure function is given the constraints of the layout and returns
+/// the size of the content. It is called when solving the min constraints,
+/// and again once the width of the layout is known, so that content like
+/// wrapped text can report it's height for the width it was given. Only
+/// fit and flex sizes use the measured size.
+///
+/// # Example
+/// ```
+/// use flow::{MeasuredLayout,VerticalLayout,Size,Layout};
+///
+/// // 350 pixels of text, wrapped to fit the max width
+/// let text = MeasuredLayout::new(|constraints| {
+///     let lines = (350.0 / constraints.max_width).ceil();
+///     Size::new(constraints.max_width.min(350.0), lines * 20.0)
+/// })
+/// .fill_width();
+///
+/// let mut layout = VerticalLayout::new().fixed_width(100.0);
+/// layout.push(text);
+///
+/// flow::solve_layout(&mut layout,Size::unit(1000.0)).unwrap();
+/// assert_eq!(layout.children()[0].size(),Size::new(100.0, 80.0));
+/// ```
+pub struct MeasuredLayout {
+    id: GlobalId,
+    size: Size<f32>,
+    position: Position<f32>,
+    constraints: BoxConstraints,
+    cache: LayoutCache,
+    measure: Measure,
+    measure_baseline: Option<MeasureBaseline>,
+    baseline: Option<f32>,
+    pub intrinsic_width: BoxSizing,
+    pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    /// Skip this layout and it's children when hit testing
+    pub non_interactive: bool,
+    pub margin: Margin,
+    /// How much the layout shrinks, relative to it's siblings, when it's
+    /// parent runs out of space. Defaults to zero, so layouts keep their
+    /// size unless they opt in.
+    pub shrink: f32,
+    direction: LayoutDirection,
+    scale_factor: Option<f32>,
+}
+
+impl MeasuredLayout {
+    pub fn new(measure: impl Fn(BoxConstraints) -> Size<f32> + 'static) -> Self {
+        Self {
+            id: GlobalId::new(),
+            size: Size::default(),
+            position: Position::default(),
+            constraints: BoxConstraints::new(),
+            cache: LayoutCache::default(),
+            measure: Box::new(measure),
+            measure_baseline: None,
+            baseline: None,
+            intrinsic_width: BoxSizing::default(),
+            intrinsic_height: BoxSizing::default(),
+            bounds: SizeBounds::default(),
+            non_interactive: false,
+            margin: Margin::default(),
+            shrink: 0.0,
+            direction: LayoutDirection::default(),
+            scale_factor: None,
+        }
+    }
+
+    /// Measure the content of the layout
+    pub fn measure(&self, constraints: BoxConstraints) -> Size<f32> {
+        (self.measure)(constraints)
+    }
+
+    /// Set a function that finds the baseline of the content, measured
+    /// from the top, given the size of the layout.
+    ///
+    /// # Example
+    /// ```
+    /// use flow::{MeasuredLayout,Size,Layout};
+    ///
+    /// // A line of text with an ascent of 16 pixels
+    /// let mut text = MeasuredLayout::new(|_| Size::new(100.0, 20.0))
+    ///     .measure_baseline(|_| 16.0);
+    ///
+    /// flow::solve_layout(&mut text,Size::unit(1000.0)).unwrap();
+    /// assert_eq!(text.baseline(),Some(16.0));
+    /// ```
+    pub fn measure_baseline(mut self, baseline: impl Fn(Size<f32>) -> f32 + 'static) -> Self {
+        self.measure_baseline = Some(Box::new(baseline));
+        self
+    }
+
+    /// Measure the baseline of the content at the given size
+    fn find_baseline(&self, size: Size<f32>) -> Option<f32> {
+        self.measure_baseline
+            .as_ref()
+            .map(|baseline| baseline(size))
+    }
+
+    /// Measure the content with a max size, where `None` is unlimited
+    fn measure_within(&self, width: Option<f32>, height: Option<f32>) -> Size<f32> {
+        self.measure(BoxConstraints {
+            max_width: width.unwrap_or(f32::INFINITY),
+            max_height: height.unwrap_or(f32::INFINITY),
+            ..BoxConstraints::new()
+        })
+    }
+
+    impl_size!();
+    impl_margin!();
+    impl_shrink!();
+}
+
+impl Debug for MeasuredLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MeasuredLayout")
+            .field("id", &self.id)
+            .field("size", &self.size)
+            .field("position", &self.position)
+            .field("constraints", &self.constraints)
+            .field("intrinsic_width", &self.intrinsic_width)
+            .field("intrinsic_height", &self.intrinsic_height)
+            .field("bounds", &self.bounds)
+            .field("non_interactive", &self.non_interactive)
+            .field("margin", &self.margin)
+            .field("shrink", &self.shrink)
+            .field("direction", &self.direction)
+            .field("scale_factor", &self.scale_factor)
+            .field("baseline", &self.baseline)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Layout for MeasuredLayout {
+    fn solve_max_constraints(&mut self) {
+        if self.cache.skip(self.constraints) {
+            return;
+        }
+
+        constrain_aspect_ratio(self);
+    }
+
+    fn position_children(&mut self) {}
+
+    fn min_content_width(&self, height: Option<f32>) -> f32 {
+        self.intrinsic_width.or_content(|| {
+            let width = self.measure_within(Some(0.0), height).width;
+            self.bounds.clamp_width(width)
+        })
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.intrinsic_width.or_content(|| {
+            let width = self.measure_within(None, height).width;
+            self.bounds.clamp_width(width)
+        })
+    }
+
+    fn min_content_height(&self, width: Option<f32>) -> f32 {
+        self.intrinsic_height.or_content(|| {
+            let height = self.measure_within(width, None).height;
+            self.bounds.clamp_height(height)
+        })
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.min_content_height(width)
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if !self.cache.recomputed() {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        let size = self.measure(self.constraints);
+
+        match self.intrinsic_width {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_width(size.width),
+            BoxSizing::Fixed(width) => self.set_min_width(width),
+            BoxSizing::Percent(_) => self.set_min_width(self.constraints.max_width),
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_height(size.height),
+            BoxSizing::Fixed(height) => self.set_min_height(height),
+            BoxSizing::Percent(_) => self.set_min_height(self.constraints.max_height),
+        }
+
+        constrain_aspect_ratio(self);
+        let size = Size::new(self.constraints.min_width, self.constraints.min_height);
+        self.baseline = self.find_baseline(size);
+        (self.constraints.min_width, self.constraints.min_height)
+    }
+
+    fn update_size(&mut self) {
+        if !self.cache.begin_update() {
+            return;
+        }
+
+        match self.intrinsic_width {
+            BoxSizing::Fit => self.size.width = self.constraints.min_width,
+            BoxSizing::Fixed(width) => self.size.width = width,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.width = self.constraints.max_width
+            }
+        }
+
+        // Measure the content again now that the width is known
+        if self.intrinsic_height == BoxSizing::Fit && self.bounds.aspect_ratio.is_none() {
+            let constraints = BoxConstraints {
+                max_width: self.size.width,
+                ..self.constraints
+            };
+            let height = self.measure(constraints).height;
+            self.set_min_height(height);
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit => self.size.height = self.constraints.min_height,
+            BoxSizing::Fixed(height) => self.size.height = height,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.height = self.constraints.max_height
+            }
+        }
+
+        self.baseline = self.find_baseline(self.size);
+    }
+
+    fn baseline(&self) -> Option<f32> {
+        self.baseline
+    }
+
+    impl_layout!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measure_with_constraints() {
+        let layout = MeasuredLayout::new(|c| Size::new(c.max_width / 2.0, 10.0));
+        let mut constraints = BoxConstraints::new();
+        constraints.max_width = 80.0;
+
+        assert_eq!(layout.measure(constraints), Size::new(40.0, 10.0));
+    }
+}
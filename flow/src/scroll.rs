@@ -0,0 +1,387 @@
+use crate::absolute::{constrain_absolute, in_flow, position_absolute};
+use crate::cache::LayoutCache;
+use crate::content::{ContentSize, outer_height, outer_width};
+use crate::snap::snap_edges;
+use crate::{
+    Axis, BoxConstraints, BoxSizing, Layout, LayoutDirection, Margin, SizeBounds,
+    constrain_aspect_ratio, impl_layout, impl_margin, impl_shrink, impl_size,
+};
+use ruby_core::{GlobalId, Position, Size};
+
+/// A viewport that scrolls it's child along one axis.
+///
+/// The child is given unbounded max constraints along the scroll axis, so
+/// it can be as large as it's content, while the viewport is sized like
+/// any other layout. A fit viewport grows with it's content until it
+/// reaches the max size, flex children fill the viewport. The child is
+/// moved back by the scroll offset, and is not reported as overflowing.
+/// Once the viewport is pushed into a parent, it can still be scrolled
+/// through [`Layout::set_scroll_offset`].
+///
+/// # Example
+/// ```
+/// use flow::{EmptyLayout,ScrollLayout,VerticalLayout,Layout,Position,Size};
+///
+/// let mut list = VerticalLayout::new();
+/// for _ in 0..10 {
+///     list.push(EmptyLayout::new().fixed(100.0, 50.0));
+/// }
+///
+/// let mut layout = ScrollLayout::new(list).fixed(100.0, 200.0);
+/// layout.set_offset(100.0);
+/// flow::solve_layout(&mut layout,Size::unit(1000.0)).unwrap();
+///
+/// assert_eq!(layout.content_size(),Size::new(100.0, 500.0));
+/// assert_eq!(layout.max_offset(),300.0);
+/// assert_eq!(layout.child().position(),Position::new(0.0, -100.0));
+/// ```
+#[derive(Debug)]
+pub struct ScrollLayout {
+    id: GlobalId,
+    pub intrinsic_width: BoxSizing,
+    pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    /// Skip this layout and it's children when hit testing
+    pub non_interactive: bool,
+    pub margin: Margin,
+    /// How much the layout shrinks, relative to it's siblings, when it's
+    /// parent runs out of space. Defaults to zero, so layouts keep their
+    /// size unless they opt in.
+    pub shrink: f32,
+    direction: LayoutDirection,
+    scale_factor: Option<f32>,
+    /// The axis that the child scrolls along
+    pub axis: Axis,
+    size: Size<f32>,
+    position: Position<f32>,
+    offset: f32,
+    child: Box<dyn Layout>,
+    constraints: BoxConstraints,
+    cache: LayoutCache,
+}
+
+impl ScrollLayout {
+    /// Create a layout that scrolls vertically
+    pub fn new(child: impl Layout + 'static) -> Self {
+        Self {
+            id: GlobalId::new(),
+            intrinsic_width: BoxSizing::default(),
+            intrinsic_height: BoxSizing::default(),
+            bounds: SizeBounds::default(),
+            non_interactive: false,
+            margin: Margin::default(),
+            shrink: 0.0,
+            direction: LayoutDirection::default(),
+            scale_factor: None,
+            axis: Axis::Vertical,
+            size: Size::default(),
+            position: Position::default(),
+            offset: 0.0,
+            child: Box::new(child),
+            constraints: BoxConstraints::new(),
+            cache: LayoutCache::default(),
+        }
+    }
+
+    /// Set the axis to scroll along
+    pub fn axis(mut self, axis: Axis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    pub fn child(&self) -> &dyn Layout {
+        self.child.as_ref()
+    }
+
+    /// Get the scroll offset
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    /// Set the scroll offset. The offset is clamped between zero and the
+    /// [`max_offset`] the next time the layout is solved.
+    ///
+    /// [`max_offset`]: ScrollLayout::max_offset
+    pub fn set_offset(&mut self, offset: f32) {
+        self.offset = offset;
+        self.mark_dirty();
+    }
+
+    /// Move the scroll offset by `delta`
+    pub fn scroll_by(&mut self, delta: f32) {
+        self.set_offset(self.offset + delta);
+    }
+
+    /// Get the size of the scrolled content, including the child's margin
+    pub fn content_size(&self) -> Size<f32> {
+        let margin = self.child.margin();
+        let size = self.child.size();
+        Size::new(
+            size.width + margin.horizontal(),
+            size.height + margin.vertical(),
+        )
+    }
+
+    /// Get the largest offset that still keeps the viewport filled
+    pub fn max_offset(&self) -> f32 {
+        let content = self.content_size();
+        let max_offset = match self.axis {
+            Axis::Horizontal => content.width - self.size.width,
+            Axis::Vertical => content.height - self.size.height,
+        };
+        max_offset.max(0.0)
+    }
+
+    /// Get the position and size of the part of the content that is
+    /// visible, relative to the top left of the content.
+    pub fn visible_rect(&self) -> (Position<f32>, Size<f32>) {
+        let position = match self.axis {
+            Axis::Horizontal => Position::new(self.offset, 0.0),
+            Axis::Vertical => Position::new(0.0, self.offset),
+        };
+        (position, self.size)
+    }
+
+    /// Get the max size of the child along an axis, which is unbounded
+    /// for fit children along the scroll axis.
+    fn child_max(&self, sizing: BoxSizing, available: f32, axis: Axis) -> f32 {
+        match sizing {
+            BoxSizing::Fixed(size) => size,
+            BoxSizing::Fit if axis == self.axis => f32::INFINITY,
+            BoxSizing::Fit | BoxSizing::Flex(_) => available,
+            BoxSizing::Percent(percent) => BoxSizing::percent_of(percent, available),
+        }
+    }
+
+    /// Get the content width of the layout. The viewport can shrink to
+    /// nothing along the scroll axis, so it's min content width is zero
+    /// when scrolling horizontally.
+    fn measure_width(&self, size: ContentSize, height: Option<f32>) -> f32 {
+        self.intrinsic_width.or_content(|| {
+            if self.axis == Axis::Horizontal && size == ContentSize::Min {
+                return self.bounds.clamp_width(0.0);
+            }
+            let width = outer_width(self.child.as_ref(), size, height);
+            self.bounds.clamp_width(width)
+        })
+    }
+
+    /// Get the content height of the layout. The viewport can shrink to
+    /// nothing along the scroll axis, so it's min content height is zero
+    /// when scrolling vertically.
+    fn measure_height(&self, size: ContentSize, width: Option<f32>) -> f32 {
+        self.intrinsic_height.or_content(|| {
+            if self.axis == Axis::Vertical && size == ContentSize::Min {
+                return self.bounds.clamp_height(0.0);
+            }
+            let height = outer_height(self.child.as_ref(), size, width);
+            self.bounds.clamp_height(height)
+        })
+    }
+
+    impl_size!();
+    impl_margin!();
+    impl_shrink!();
+}
+
+impl Layout for ScrollLayout {
+    fn solve_max_constraints(&mut self) {
+        if self.cache.skip(self.constraints) {
+            return;
+        }
+
+        self.child.set_direction(self.direction);
+        self.child.set_scale_factor(self.scale_factor);
+
+        constrain_aspect_ratio(self);
+        // Absolutely positioned children are constrained once the size
+        // of this layout is known
+        if !in_flow(self.child.as_ref()) {
+            return;
+        }
+
+        let margin = self.child.margin();
+        let width = self.child_max(
+            self.child.intrinsic_width(),
+            self.constraints.max_width - margin.horizontal(),
+            Axis::Horizontal,
+        );
+        let height = self.child_max(
+            self.child.intrinsic_height(),
+            self.constraints.max_height - margin.vertical(),
+            Axis::Vertical,
+        );
+        self.child.set_max_width(width);
+        self.child.set_max_height(height);
+        self.child.solve_max_constraints();
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if !self.cache.recomputed() {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        let (mut min_width, mut min_height) = self.child.solve_min_constraints();
+        if in_flow(self.child.as_ref()) {
+            min_width += self.child.margin().horizontal();
+            min_height += self.child.margin().vertical();
+        } else {
+            (min_width, min_height) = (0.0, 0.0);
+        }
+
+        // The viewport doesn't grow past the max size along the
+        // scroll axis
+        match self.axis {
+            Axis::Horizontal => min_width = min_width.min(self.constraints.max_width),
+            Axis::Vertical => min_height = min_height.min(self.constraints.max_height),
+        }
+
+        match self.intrinsic_width {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_width(min_width),
+            BoxSizing::Fixed(width) => self.set_min_width(width),
+            BoxSizing::Percent(_) => self.set_min_width(self.constraints.max_width),
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_height(min_height),
+            BoxSizing::Fixed(height) => self.set_min_height(height),
+            BoxSizing::Percent(_) => self.set_min_height(self.constraints.max_height),
+        }
+
+        constrain_aspect_ratio(self);
+        (self.constraints.min_width, self.constraints.min_height)
+    }
+
+    fn update_size(&mut self) {
+        if !self.cache.begin_update() {
+            return;
+        }
+
+        match self.intrinsic_width {
+            BoxSizing::Fit => self.size.width = self.constraints.min_width,
+            BoxSizing::Fixed(width) => self.size.width = width,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.width = self.constraints.max_width
+            }
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit => self.size.height = self.constraints.min_height,
+            BoxSizing::Fixed(height) => self.size.height = height,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.height = self.constraints.max_height
+            }
+        }
+
+        constrain_absolute(self.child.as_mut(), self.size);
+        self.child.update_size();
+    }
+
+    fn position_children(&mut self) {
+        if self.cache.skip_position(self.position) {
+            return;
+        }
+
+        if !in_flow(self.child.as_ref()) {
+            position_absolute(self.child.as_mut(), self.position, self.size);
+            return;
+        }
+
+        self.offset = self.offset.clamp(0.0, self.max_offset());
+        let margin = self.child.margin();
+        let x = self.position.x + margin.left as f32;
+        let y = self.position.y + margin.top as f32;
+        let position = match self.axis {
+            Axis::Horizontal => Position::new(x - self.offset, y),
+            Axis::Vertical => Position::new(x, y - self.offset),
+        };
+        let width = self.child.size().width;
+        let x = self
+            .direction
+            .place(position.x, width, self.position.x, self.size.width);
+        let position = Position::new(x, position.y);
+
+        self.child.set_position(position);
+        self.child.position_children();
+    }
+
+    fn snap_to_pixels(&mut self) {
+        snap_edges(self);
+        self.child.snap_to_pixels();
+    }
+
+    fn min_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Min, height)
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Max, height)
+    }
+
+    fn min_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Min, width)
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Max, width)
+    }
+
+    fn clips_children(&self) -> bool {
+        true
+    }
+
+    fn scroll_offset(&self) -> Option<f32> {
+        Some(self.offset)
+    }
+
+    fn set_scroll_offset(&mut self, offset: f32) {
+        self.set_offset(offset);
+    }
+
+    fn max_scroll_offset(&self) -> Option<f32> {
+        Some(self.max_offset())
+    }
+
+    fn children(&self) -> Vec<&dyn Layout> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Layout> {
+        self.mark_dirty();
+        vec![self.child.as_mut()]
+    }
+
+    fn replace_child(&mut self, id: GlobalId, child: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        if self.child.id() != id {
+            return None;
+        }
+        self.mark_dirty();
+        Some(std::mem::replace(&mut self.child, child))
+    }
+
+    impl_layout!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EmptyLayout;
+
+    #[test]
+    fn unbounded_along_scroll_axis() {
+        let layout = ScrollLayout::new(EmptyLayout::new());
+
+        assert_eq!(
+            layout.child_max(BoxSizing::Fit, 100.0, Axis::Vertical),
+            f32::INFINITY
+        );
+        assert_eq!(
+            layout.child_max(BoxSizing::Fit, 100.0, Axis::Horizontal),
+            100.0
+        );
+        assert_eq!(
+            layout.child_max(BoxSizing::Flex(1.0), 100.0, Axis::Vertical),
+            100.0
+        );
+    }
+}
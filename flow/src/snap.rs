@@ -0,0 +1,67 @@
+use crate::{Layout, Position, Size};
+
+/// Round the edges of a layout to whole device pixels, if it has a scale
+/// factor. Each edge is rounded on it's own, rather than the size, so that
+/// layouts which touched before rounding still touch afterwards and the
+/// total size of a row of layouts doesn't change.
+///
+/// This only changes the layout itself, so it must be called before the
+/// children are snapped and while their edges are still exact.
+pub(crate) fn snap_edges(layout: &mut (impl Layout + ?Sized)) {
+    let Some(scale) = layout.scale_factor() else {
+        return;
+    };
+    if scale <= 0.0 || !scale.is_finite() {
+        return;
+    }
+
+    let round = |value: f32| (value * scale).round() / scale;
+    let position = layout.position();
+    let size = layout.size();
+    let x = round(position.x);
+    let y = round(position.y);
+    let right = round(position.x + size.width);
+    let bottom = round(position.y + size.height);
+
+    layout.set_position(Position::new(x, y));
+    layout.set_size(Size::new(right - x, bottom - y));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EmptyLayout;
+
+    #[test]
+    fn round_edges() {
+        let mut layout = EmptyLayout::new();
+        layout.set_scale_factor(Some(1.0));
+        layout.set_position(Position::new(333.333, 0.4));
+        layout.set_size(Size::new(333.333, 10.2));
+        snap_edges(&mut layout);
+
+        assert_eq!(layout.position(), Position::new(333.0, 0.0));
+        assert_eq!(layout.size(), Size::new(334.0, 11.0));
+    }
+
+    #[test]
+    fn round_to_device_pixels() {
+        let mut layout = EmptyLayout::new();
+        layout.set_scale_factor(Some(2.0));
+        layout.set_position(Position::new(10.3, 0.0));
+        layout.set_size(Size::new(20.1, 10.0));
+        snap_edges(&mut layout);
+
+        assert_eq!(layout.position().x, 10.5);
+        assert_eq!(layout.size().width, 20.0);
+    }
+
+    #[test]
+    fn no_scale_factor() {
+        let mut layout = EmptyLayout::new();
+        layout.set_position(Position::new(0.5, 0.5));
+        snap_edges(&mut layout);
+
+        assert_eq!(layout.position(), Position::new(0.5, 0.5));
+    }
+}
@@ -0,0 +1,407 @@
+use crate::absolute::{constrain_absolute, in_flow, position_absolute};
+use crate::cache::LayoutCache;
+use crate::content::{ContentSize, fit_height, fit_width, outer_height, outer_width};
+use crate::snap::snap_edges;
+use crate::{
+    BoxConstraints, BoxSizing, Layout, LayoutDirection, Margin, Padding, Position, Size,
+    SizeBounds, constrain_aspect_ratio, impl_layout, impl_margin, impl_padding, impl_shrink,
+    impl_size,
+};
+use ruby_core::GlobalId;
+
+/// The anchor point of a child inside a [`StackLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    #[default]
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl Alignment {
+    /// Get the horizontal and vertical fraction of the free space
+    /// to place a child at.
+    fn factors(&self) -> (f32, f32) {
+        match self {
+            Self::TopLeft => (0.0, 0.0),
+            Self::TopCenter => (0.5, 0.0),
+            Self::TopRight => (1.0, 0.0),
+            Self::CenterLeft => (0.0, 0.5),
+            Self::Center => (0.5, 0.5),
+            Self::CenterRight => (1.0, 0.5),
+            Self::BottomLeft => (0.0, 1.0),
+            Self::BottomCenter => (0.5, 1.0),
+            Self::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+/// A [`Layout`] that places it's children on top of each other, with
+/// later children drawn above earlier ones.
+///
+/// When the stack fits it's content it is as large as it's largest
+/// child. Children with a flex size fill the stack.
+///
+/// # Example
+/// ```
+/// use flow::{StackLayout,EmptyLayout,Alignment,Position,Size,Layout};
+///
+/// let mut stack = StackLayout::new();
+/// stack.push(EmptyLayout::new().fixed(100.0, 100.0));
+/// stack.place(
+///     EmptyLayout::new().fixed(20.0, 20.0),
+///     Alignment::TopRight,
+///     Position::new(5.0, -5.0),
+/// );
+///
+/// flow::solve_layout(&mut stack,Size::unit(1000.0)).unwrap();
+/// assert_eq!(stack.size(),Size::unit(100.0));
+/// assert_eq!(stack.children()[1].position(),Position::new(85.0, -5.0));
+/// ```
+#[derive(Debug, Default)]
+pub struct StackLayout {
+    id: GlobalId,
+    size: Size<f32>,
+    position: Position<f32>,
+    children: Vec<Box<dyn Layout>>,
+    placements: Vec<(Alignment, Position<f32>)>,
+    pub padding: Padding,
+    pub intrinsic_width: BoxSizing,
+    pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    /// Skip this layout and it's children when hit testing
+    pub non_interactive: bool,
+    pub margin: Margin,
+    /// How much the layout shrinks, relative to it's siblings, when it's
+    /// parent runs out of space. Defaults to zero, so layouts keep their
+    /// size unless they opt in.
+    pub shrink: f32,
+    direction: LayoutDirection,
+    scale_factor: Option<f32>,
+    constraints: BoxConstraints,
+    cache: LayoutCache,
+}
+
+impl StackLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn children(&self) -> &[Box<dyn Layout>] {
+        self.children.as_slice()
+    }
+
+    /// Add a child to the top left of the stack.
+    pub fn push(&mut self, child: impl Layout + 'static) {
+        self.place(child, Alignment::default(), Position::default());
+    }
+
+    /// Insert a child at `index`, in the top left of the stack. Children
+    /// later in the list are drawn above earlier children.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of children.
+    pub fn insert(&mut self, index: usize, child: impl Layout + 'static) {
+        self.mark_dirty();
+        self.children.insert(index, Box::new(child));
+        self.placements
+            .insert(index, (Alignment::default(), Position::default()));
+    }
+
+    /// Add a child anchored to the `alignment` point of the stack
+    /// and moved by `offset`.
+    pub fn place(
+        &mut self,
+        child: impl Layout + 'static,
+        alignment: Alignment,
+        offset: Position<f32>,
+    ) {
+        self.mark_dirty();
+        self.children.push(Box::new(child));
+        self.placements.push((alignment, offset));
+    }
+
+    /// Get the content width of the layout, which is the width of the
+    /// widest child
+    fn measure_width(&self, size: ContentSize, height: Option<f32>) -> f32 {
+        self.intrinsic_width.or_content(|| {
+            let height = height.map(|height| height - self.padding.vertical());
+            let width = self
+                .children
+                .iter()
+                .map(|child| outer_width(child.as_ref(), size, height))
+                .fold(0.0, f32::max);
+            self.bounds.clamp_width(width + self.padding.horizontal())
+        })
+    }
+
+    /// Get the content height of the layout, which is the height of the
+    /// tallest child
+    fn measure_height(&self, size: ContentSize, width: Option<f32>) -> f32 {
+        self.intrinsic_height.or_content(|| {
+            let width = width.map(|width| width - self.padding.horizontal());
+            let height = self
+                .children
+                .iter()
+                .map(|child| outer_height(child.as_ref(), size, width))
+                .fold(0.0, f32::max);
+            self.bounds.clamp_height(height + self.padding.vertical())
+        })
+    }
+
+    impl_size!();
+    impl_margin!();
+    impl_shrink!();
+    impl_padding!();
+}
+
+impl Layout for StackLayout {
+    fn solve_max_constraints(&mut self) {
+        if self.cache.skip(self.constraints) {
+            return;
+        }
+
+        for child in &mut self.children {
+            child.set_direction(self.direction);
+            child.set_scale_factor(self.scale_factor);
+        }
+
+        constrain_aspect_ratio(self);
+        let padding = self.padding.left + self.padding.right;
+        let max_width = self.constraints.max_width - padding as f32;
+        let padding = self.padding.top + self.padding.bottom;
+        let max_height = self.constraints.max_height - padding as f32;
+
+        for child in &mut self.children {
+            // Absolutely positioned children are constrained once
+            // the size of this layout is known
+            if !in_flow(child.as_ref()) {
+                continue;
+            }
+
+            let margin = child.margin();
+            match child.intrinsic_width() {
+                BoxSizing::Fit => {
+                    let available = max_width - margin.horizontal();
+                    child.set_max_width(fit_width(child.as_ref(), available, None));
+                }
+                BoxSizing::Flex(_) => child.set_max_width(max_width - margin.horizontal()),
+                BoxSizing::Fixed(width) => child.set_max_width(width),
+                BoxSizing::Percent(percent) => {
+                    child.set_max_width(BoxSizing::percent_of(percent, max_width));
+                }
+            }
+
+            match child.intrinsic_height() {
+                BoxSizing::Fit => {
+                    let available = max_height - margin.vertical();
+                    let width = Some(child.constraints().max_width);
+                    child.set_max_height(fit_height(child.as_ref(), available, width));
+                }
+                BoxSizing::Flex(_) => child.set_max_height(max_height - margin.vertical()),
+                BoxSizing::Fixed(height) => child.set_max_height(height),
+                BoxSizing::Percent(percent) => {
+                    child.set_max_height(BoxSizing::percent_of(percent, max_height));
+                }
+            }
+
+            child.solve_max_constraints();
+        }
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if !self.cache.recomputed() {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        let mut min_width: f32 = 0.0;
+        let mut min_height: f32 = 0.0;
+
+        for child in &mut self.children {
+            let (width, height) = child.solve_min_constraints();
+            if !in_flow(child.as_ref()) {
+                continue;
+            }
+            let margin = child.margin();
+            min_width = min_width.max(width + margin.horizontal());
+            min_height = min_height.max(height + margin.vertical());
+        }
+
+        let padding = self.padding.left + self.padding.right;
+        min_width += padding as f32;
+        let padding = self.padding.top + self.padding.bottom;
+        min_height += padding as f32;
+
+        match self.intrinsic_width {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_width(min_width),
+            BoxSizing::Fixed(width) => self.set_min_width(width),
+            BoxSizing::Percent(_) => self.set_min_width(self.constraints.max_width),
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_height(min_height),
+            BoxSizing::Fixed(height) => self.set_min_height(height),
+            BoxSizing::Percent(_) => self.set_min_height(self.constraints.max_height),
+        }
+
+        constrain_aspect_ratio(self);
+        (self.constraints.min_width, self.constraints.min_height)
+    }
+
+    fn update_size(&mut self) {
+        if !self.cache.begin_update() {
+            return;
+        }
+
+        match self.intrinsic_width {
+            BoxSizing::Fit => self.size.width = self.constraints.min_width,
+            BoxSizing::Fixed(width) => self.size.width = width,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.width = self.constraints.max_width
+            }
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit => self.size.height = self.constraints.min_height,
+            BoxSizing::Fixed(height) => self.size.height = height,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.height = self.constraints.max_height
+            }
+        }
+
+        // Flex children fill the stack, which is only known once the
+        // stack's size has been solved
+        let padding = self.padding.left + self.padding.right;
+        let width = self.size.width - padding as f32;
+        let padding = self.padding.top + self.padding.bottom;
+        let height = self.size.height - padding as f32;
+
+        for child in &mut self.children {
+            if !in_flow(child.as_ref()) {
+                constrain_absolute(child.as_mut(), self.size);
+                child.update_size();
+                continue;
+            }
+
+            let flex_width = matches!(child.intrinsic_width(), BoxSizing::Flex(_));
+            let flex_height = matches!(child.intrinsic_height(), BoxSizing::Flex(_));
+            let margin = child.margin();
+            if flex_width {
+                child.set_max_width(width - margin.horizontal());
+            }
+            if flex_height {
+                child.set_max_height(height - margin.vertical());
+            }
+            if flex_width || flex_height {
+                child.solve_max_constraints();
+            }
+            child.update_size();
+        }
+    }
+
+    fn position_children(&mut self) {
+        if self.cache.skip_position(self.position) {
+            return;
+        }
+
+        let padding = self.padding.left + self.padding.right;
+        let width = self.size.width - padding as f32;
+        let padding = self.padding.top + self.padding.bottom;
+        let height = self.size.height - padding as f32;
+
+        for (child, (alignment, offset)) in self.children.iter_mut().zip(&self.placements) {
+            if !in_flow(child.as_ref()) {
+                position_absolute(child.as_mut(), self.position, self.size);
+                continue;
+            }
+
+            let (x_factor, y_factor) = alignment.factors();
+            let margin = child.margin();
+            let free_width = width - child.size().width - margin.horizontal();
+            let free_height = height - child.size().height - margin.vertical();
+
+            let x = self.position.x + self.padding.left as f32 + margin.left as f32;
+            let y = self.position.y + self.padding.top as f32 + margin.top as f32;
+            let x = x + free_width * x_factor + offset.x;
+            let y = y + free_height * y_factor;
+            let width = child.size().width;
+
+            child.set_position(Position {
+                x: self
+                    .direction
+                    .place(x, width, self.position.x, self.size.width),
+                y: y + offset.y,
+            });
+            child.position_children();
+        }
+    }
+
+    fn snap_to_pixels(&mut self) {
+        snap_edges(self);
+        for child in &mut self.children {
+            child.snap_to_pixels();
+        }
+    }
+
+    fn min_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Min, height)
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Max, height)
+    }
+
+    fn min_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Min, width)
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Max, width)
+    }
+
+    fn children(&self) -> Vec<&dyn Layout> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Layout> {
+        self.mark_dirty();
+        self.children
+            .iter_mut()
+            .map(|c| c.as_mut() as &mut dyn Layout)
+            .collect()
+    }
+
+    fn remove_child(&mut self, id: GlobalId) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        self.placements.remove(index);
+        Some(self.children.remove(index))
+    }
+
+    fn replace_child(&mut self, id: GlobalId, child: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        Some(std::mem::replace(&mut self.children[index], child))
+    }
+
+    impl_layout!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alignment_factors() {
+        assert_eq!(Alignment::TopLeft.factors(), (0.0, 0.0));
+        assert_eq!(Alignment::Center.factors(), (0.5, 0.5));
+        assert_eq!(Alignment::BottomRight.factors(), (1.0, 1.0));
+        assert_eq!(Alignment::CenterRight.factors(), (1.0, 0.5));
+    }
+}
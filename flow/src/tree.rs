@@ -0,0 +1,731 @@
+use crate::error::{Rect, check_flex, check_overflow, check_size};
+use crate::{
+    Axis, BoxConstraints, BoxSizing, CrossAxisAlignment, LayoutError, LayoutWarning,
+    MainAxisAlignment, Padding, SizeBounds, distribute_flex, impl_alignment, impl_padding,
+    impl_size,
+};
+use ruby_core::{GlobalId, Position, Size};
+use std::collections::HashMap;
+
+/// A handle to a [`Node`] in a [`LayoutTree`].
+///
+/// Handles to removed nodes are never reused, even if the slot in the
+/// tree is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    index: u32,
+    generation: u32,
+}
+
+/// The way a [`Node`] arranges it's children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeKind {
+    /// A leaf node, that can't have any children
+    #[default]
+    Empty,
+    /// Arrange the children horizontally, like a [`HorizontalLayout`]
+    ///
+    /// [`HorizontalLayout`]: crate::HorizontalLayout
+    Horizontal,
+    /// Arrange the children vertically, like a [`VerticalLayout`]
+    ///
+    /// [`VerticalLayout`]: crate::VerticalLayout
+    Vertical,
+}
+
+/// An error from changing the structure of a [`LayoutTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum TreeError {
+    #[error("node {0:?} is not in the tree")]
+    NotFound(NodeId),
+    #[error("node {0:?} is an empty node and can't have children")]
+    NotAContainer(NodeId),
+    #[error("node {child:?} can't be moved into it's own subtree at {parent:?}")]
+    Cycle { child: NodeId, parent: NodeId },
+}
+
+/// A single layout stored in a [`LayoutTree`].
+#[derive(Debug, Default, Clone)]
+pub struct Node {
+    id: GlobalId,
+    size: Size<f32>,
+    position: Position<f32>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    pub kind: NodeKind,
+    pub spacing: u32,
+    pub padding: Padding,
+    pub main_axis_alignment: MainAxisAlignment,
+    pub cross_axis_alignment: CrossAxisAlignment,
+    pub intrinsic_width: BoxSizing,
+    pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    constraints: BoxConstraints,
+    /// The size of the content, from the last solve
+    content: Size<f32>,
+}
+
+impl Node {
+    pub fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            ..Self::default()
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(NodeKind::Empty)
+    }
+
+    pub fn horizontal() -> Self {
+        Self::new(NodeKind::Horizontal)
+    }
+
+    pub fn vertical() -> Self {
+        Self::new(NodeKind::Vertical)
+    }
+
+    pub fn id(&self) -> GlobalId {
+        self.id
+    }
+
+    pub fn size(&self) -> Size<f32> {
+        self.size
+    }
+
+    pub fn position(&self) -> Position<f32> {
+        self.position
+    }
+
+    pub fn constraints(&self) -> BoxConstraints {
+        self.constraints
+    }
+
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+
+    impl_size!();
+    impl_padding!();
+    impl_alignment!();
+
+    /// The axis that the children are laid out along
+    fn main_axis(&self) -> Option<Axis> {
+        match self.kind {
+            NodeKind::Empty => None,
+            NodeKind::Horizontal => Some(Axis::Horizontal),
+            NodeKind::Vertical => Some(Axis::Vertical),
+        }
+    }
+
+    fn sizing(&self, axis: Axis) -> BoxSizing {
+        match axis {
+            Axis::Horizontal => self.intrinsic_width,
+            Axis::Vertical => self.intrinsic_height,
+        }
+    }
+
+    fn max(&self, axis: Axis) -> f32 {
+        match axis {
+            Axis::Horizontal => self.constraints.max_width,
+            Axis::Vertical => self.constraints.max_height,
+        }
+    }
+
+    fn min(&self, axis: Axis) -> f32 {
+        match axis {
+            Axis::Horizontal => self.constraints.min_width,
+            Axis::Vertical => self.constraints.min_height,
+        }
+    }
+
+    fn set_max(&mut self, axis: Axis, value: f32) {
+        match axis {
+            Axis::Horizontal => self.constraints.max_width = self.bounds.clamp_width(value),
+            Axis::Vertical => self.constraints.max_height = self.bounds.clamp_height(value),
+        }
+    }
+
+    fn set_min(&mut self, axis: Axis, value: f32) {
+        match axis {
+            Axis::Horizontal => self.constraints.min_width = self.bounds.clamp_width(value),
+            Axis::Vertical => self.constraints.min_height = self.bounds.clamp_height(value),
+        }
+    }
+
+    /// The padding at the start and end of an axis
+    fn padding_along(&self, axis: Axis) -> (f32, f32) {
+        match axis {
+            Axis::Horizontal => (self.padding.left as f32, self.padding.right as f32),
+            Axis::Vertical => (self.padding.top as f32, self.padding.bottom as f32),
+        }
+    }
+
+    /// Get the min and max bounds of the node along an axis
+    fn bounds_along(&self, axis: Axis) -> (f32, f32) {
+        match axis {
+            Axis::Horizontal => (self.bounds.min_width, self.bounds.max_width),
+            Axis::Vertical => (self.bounds.min_height, self.bounds.max_height),
+        }
+    }
+
+    /// The space available to the children along an axis
+    fn content_max(&self, axis: Axis) -> f32 {
+        let (start, end) = self.padding_along(axis);
+        self.max(axis) - start - end
+    }
+
+    fn content_along(&self, axis: Axis) -> f32 {
+        match axis {
+            Axis::Horizontal => self.content.width,
+            Axis::Vertical => self.content.height,
+        }
+    }
+
+    fn size_along(&self, axis: Axis) -> f32 {
+        match axis {
+            Axis::Horizontal => self.size.width,
+            Axis::Vertical => self.size.height,
+        }
+    }
+
+    /// Derive the constraints of one axis from the other, the same as
+    /// the layouts do.
+    fn constrain_aspect_ratio(&mut self) {
+        let Some(ratio) = self.bounds.aspect_ratio else {
+            return;
+        };
+        if !ratio.is_finite() || ratio <= 0.0 {
+            return;
+        }
+
+        match (self.intrinsic_width, self.intrinsic_height) {
+            (BoxSizing::Fixed(_), BoxSizing::Fixed(_)) => {}
+            (_, BoxSizing::Fixed(height)) => {
+                self.set_min(Axis::Horizontal, height * ratio);
+                self.set_max(Axis::Horizontal, height * ratio);
+            }
+            (width, _) => {
+                let width = match width {
+                    BoxSizing::Fixed(width) => width,
+                    BoxSizing::Flex(_) | BoxSizing::Percent(_) => self.constraints.max_width,
+                    BoxSizing::Fit => self.constraints.min_width,
+                };
+                self.set_min(Axis::Vertical, width / ratio);
+                self.set_max(Axis::Vertical, width / ratio);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Slot {
+    generation: u32,
+    node: Option<Node>,
+}
+
+/// An arena that owns a tree of [`Node`]s and refers to them by
+/// [`NodeId`].
+///
+/// The nodes are stored in a single allocation and solved with loops
+/// over the tree, rather than recursing through trait objects. Nodes
+/// share the fixed, percent, fit and flex sizing rules of
+/// [`HorizontalLayout`] and [`VerticalLayout`], but only support the
+/// settings on [`Node`]. There are no margins, shrinking, directions,
+/// pixel snapping, absolutely positioned children or measured content,
+/// so a tree using any of those has to be built from layouts instead.
+///
+/// # Example
+/// ```
+/// use flow::{LayoutTree,Node,Size,Position};
+///
+/// let mut tree = LayoutTree::new();
+/// let root = tree.insert(Node::horizontal().fixed_width(300.0));
+/// tree.set_root(root).unwrap();
+///
+/// tree.insert_child(root, Node::empty().fixed(100.0, 50.0)).unwrap();
+/// let fill = tree.insert_child(root, Node::empty().fill_width().fixed_height(50.0)).unwrap();
+///
+/// tree.solve(Size::unit(1000.0)).unwrap();
+/// assert_eq!(tree.get(fill).unwrap().size(),Size::new(200.0, 50.0));
+/// assert_eq!(tree.get(fill).unwrap().position(),Position::new(100.0, 0.0));
+/// ```
+///
+/// [`HorizontalLayout`]: crate::HorizontalLayout
+/// [`VerticalLayout`]: crate::VerticalLayout
+#[derive(Debug, Default)]
+pub struct LayoutTree {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    ids: HashMap<GlobalId, NodeId>,
+    root: Option<NodeId>,
+}
+
+impl LayoutTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of nodes in the tree
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn root(&self) -> Option<NodeId> {
+        self.root
+    }
+
+    /// Set the node that is solved by [`LayoutTree::solve`], detaching it
+    /// from it's parent.
+    pub fn set_root(&mut self, id: NodeId) -> Result<(), TreeError> {
+        self.get(id).ok_or(TreeError::NotFound(id))?;
+        self.detach(id);
+        self.root = Some(id);
+        Ok(())
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&Node> {
+        let slot = self.slots.get(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.node.as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        let slot = self.slots.get_mut(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.node.as_mut()
+    }
+
+    /// Find a node by the [`GlobalId`] of the node.
+    pub fn find(&self, id: GlobalId) -> Option<NodeId> {
+        self.ids.get(&id).copied()
+    }
+
+    /// Add a node to the tree, without a parent.
+    ///
+    /// A node that has the same [`GlobalId`] as a node already in the
+    /// tree, such as a clone of it, is given a new id.
+    pub fn insert(&mut self, mut node: Node) -> NodeId {
+        node.parent = None;
+        node.children.clear();
+        if self.ids.contains_key(&node.id) {
+            node.id = GlobalId::new();
+        }
+        let global_id = node.id;
+
+        let id = match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.node = Some(node);
+                NodeId {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    node: Some(node),
+                });
+                NodeId {
+                    index: self.slots.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        };
+
+        self.ids.insert(global_id, id);
+        id
+    }
+
+    /// Add a node to the end of the children of `parent`.
+    pub fn insert_child(&mut self, parent: NodeId, node: Node) -> Result<NodeId, TreeError> {
+        self.check_container(parent)?;
+        let id = self.insert(node);
+        self.attach(id, parent);
+        Ok(id)
+    }
+
+    /// Remove a node and all of it's descendants from the tree.
+    pub fn remove(&mut self, id: NodeId) -> Result<Node, TreeError> {
+        self.get(id).ok_or(TreeError::NotFound(id))?;
+        self.detach(id);
+        if self.root == Some(id) {
+            self.root = None;
+        }
+
+        let mut removed = None;
+        let mut stack = vec![id];
+        while let Some(current) = stack.pop() {
+            let slot = &mut self.slots[current.index as usize];
+            let mut node = slot.node.take().expect("Removed node should exist");
+            slot.generation += 1;
+            self.free.push(current.index);
+            self.ids.remove(&node.id);
+
+            stack.append(&mut node.children);
+            if current == id {
+                node.parent = None;
+                removed = Some(node);
+            }
+        }
+
+        Ok(removed.expect("Removed node should exist"))
+    }
+
+    /// Move a node, and it's descendants, to the end of the children of
+    /// `parent`.
+    pub fn reparent(&mut self, child: NodeId, parent: NodeId) -> Result<(), TreeError> {
+        self.get(child).ok_or(TreeError::NotFound(child))?;
+        self.check_container(parent)?;
+
+        // The new parent can't be inside the subtree being moved
+        let mut ancestor = Some(parent);
+        while let Some(current) = ancestor {
+            if current == child {
+                return Err(TreeError::Cycle { child, parent });
+            }
+            ancestor = self.node(current).parent;
+        }
+
+        if self.root == Some(child) {
+            self.root = None;
+        }
+        self.detach(child);
+        self.attach(child, parent);
+        Ok(())
+    }
+
+    /// Solve the size and position of every node under the root.
+    ///
+    /// See [`solve_layout`](crate::solve_layout).
+    pub fn solve(&mut self, max_size: Size<f32>) -> Result<Vec<LayoutWarning>, LayoutError> {
+        let Some(root) = self.root else {
+            return Ok(vec![]);
+        };
+
+        let order = self.preorder(root);
+        // Invalid flex weights can't be shared out, so they're checked
+        // before solving
+        for &id in &order {
+            let node = self.node(id);
+            let sizing = [
+                (Axis::Horizontal, node.intrinsic_width),
+                (Axis::Vertical, node.intrinsic_height),
+            ];
+            check_flex(node.id, sizing)?;
+        }
+
+        let node = self.node_mut(root);
+        for (axis, max) in [
+            (Axis::Horizontal, max_size.width),
+            (Axis::Vertical, max_size.height),
+        ] {
+            match node.sizing(axis) {
+                BoxSizing::Fit | BoxSizing::Flex(_) => node.set_max(axis, max),
+                BoxSizing::Fixed(size) => node.set_max(axis, size),
+                BoxSizing::Percent(percent) => {
+                    node.set_max(axis, BoxSizing::percent_of(percent, max))
+                }
+            }
+        }
+
+        // Parents are solved before their children when solving the max
+        // constraints, and after their children for the content size and
+        // min constraints
+        for &id in order.iter().rev() {
+            self.solve_content_size(id);
+        }
+        for &id in &order {
+            self.solve_max_constraints(id);
+        }
+        for &id in order.iter().rev() {
+            self.solve_min_constraints(id);
+        }
+        for &id in &order {
+            self.update_size(id);
+        }
+        for &id in &order {
+            self.position_children(id);
+        }
+
+        let mut warnings = vec![];
+        for &id in &order {
+            let node = self.node(id);
+            let parent = Rect {
+                id: node.id,
+                position: node.position,
+                size: node.size,
+            };
+            check_size(parent)?;
+
+            for &child in &node.children {
+                let child = self.node(child);
+                let child = Rect {
+                    id: child.id,
+                    position: child.position,
+                    size: child.size,
+                };
+                check_overflow(parent, child, &mut warnings);
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    fn node(&self, id: NodeId) -> &Node {
+        self.get(id).expect("Node should be in the tree")
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> &mut Node {
+        self.get_mut(id).expect("Node should be in the tree")
+    }
+
+    fn check_container(&self, id: NodeId) -> Result<(), TreeError> {
+        let node = self.get(id).ok_or(TreeError::NotFound(id))?;
+        if node.kind == NodeKind::Empty {
+            return Err(TreeError::NotAContainer(id));
+        }
+        Ok(())
+    }
+
+    fn attach(&mut self, child: NodeId, parent: NodeId) {
+        self.node_mut(parent).children.push(child);
+        self.node_mut(child).parent = Some(parent);
+    }
+
+    fn detach(&mut self, child: NodeId) {
+        let Some(parent) = self.node_mut(child).parent.take() else {
+            return;
+        };
+        self.node_mut(parent).children.retain(|&c| c != child);
+    }
+
+    /// Get the nodes in the subtree, with each parent before it's children
+    fn preorder(&self, root: NodeId) -> Vec<NodeId> {
+        let mut order = Vec::with_capacity(self.len());
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            order.push(id);
+            stack.extend(self.node(id).children.iter().rev());
+        }
+        order
+    }
+
+    /// Solve the size of a node's content, which is the size of the node
+    /// when it fits it's content. Nodes can't wrap, so this is both the
+    /// min and max content size. The content size of the children must
+    /// already be solved.
+    fn solve_content_size(&mut self, id: NodeId) {
+        let node = self.node(id);
+        let [width, height] = [Axis::Horizontal, Axis::Vertical].map(|axis| {
+            if let BoxSizing::Fixed(size) = node.sizing(axis) {
+                return size;
+            }
+
+            let mut size = 0.0;
+            if let Some(main) = node.main_axis() {
+                let children = node
+                    .children
+                    .iter()
+                    .map(|&c| self.node(c).content_along(axis));
+                size = if axis == main {
+                    let gaps = node.children.len().saturating_sub(1);
+                    children.sum::<f32>() + node.spacing as f32 * gaps as f32
+                } else {
+                    children.fold(0.0, f32::max)
+                };
+                let (start, end) = node.padding_along(axis);
+                size += start + end;
+            }
+
+            match axis {
+                Axis::Horizontal => node.bounds.clamp_width(size),
+                Axis::Vertical => node.bounds.clamp_height(size),
+            }
+        });
+        self.node_mut(id).content = Size::new(width, height);
+    }
+
+    fn solve_max_constraints(&mut self, id: NodeId) {
+        self.node_mut(id).constrain_aspect_ratio();
+        let node = self.node(id);
+        let Some(main) = node.main_axis() else {
+            return;
+        };
+        let cross = main.cross();
+
+        let content_main = node.content_max(main);
+        let content_cross = node.content_max(cross);
+        let mut used = node.spacing as f32 * node.children.len().saturating_sub(1) as f32;
+        let mut weights = vec![];
+        let mut bounds = vec![];
+        for &child in &node.children {
+            let child_node = self.node(child);
+            match child_node.sizing(main) {
+                BoxSizing::Fixed(size) => used += size,
+                BoxSizing::Percent(percent) => used += BoxSizing::percent_of(percent, content_main),
+                BoxSizing::Flex(flex) => {
+                    weights.push(flex);
+                    bounds.push(child_node.bounds_along(main));
+                }
+                BoxSizing::Fit => used += child_node.content_along(main),
+            }
+        }
+        let remaining = (content_main - used).max(0.0);
+        let mut flex_sizes = distribute_flex(remaining, &weights, &bounds).into_iter();
+
+        let children = std::mem::take(&mut self.node_mut(id).children);
+        for &child in &children {
+            let child = self.node_mut(child);
+            let content = [main, cross].map(|axis| child.content_along(axis));
+            let max = match child.sizing(main) {
+                BoxSizing::Fit => content[0],
+                BoxSizing::Fixed(size) => size,
+                BoxSizing::Percent(percent) => BoxSizing::percent_of(percent, content_main),
+                BoxSizing::Flex(_) => flex_sizes.next().unwrap_or_default(),
+            };
+            child.set_max(main, max);
+
+            let max = match child.sizing(cross) {
+                BoxSizing::Fit => content[1],
+                BoxSizing::Fixed(size) => size,
+                BoxSizing::Percent(percent) => BoxSizing::percent_of(percent, content_cross),
+                BoxSizing::Flex(_) => content_cross,
+            };
+            child.set_max(cross, max);
+        }
+        self.node_mut(id).children = children;
+    }
+
+    fn solve_min_constraints(&mut self, id: NodeId) {
+        let node = self.node(id);
+        let mut content = Size::default();
+        if let Some(main) = node.main_axis() {
+            let cross = main.cross();
+            let spacing = node.spacing as f32 * node.children.len().saturating_sub(1) as f32;
+            let (start, end) = node.padding_along(main);
+            let mut main_size = spacing + start + end;
+            let mut cross_size: f32 = 0.0;
+            for &child in &node.children {
+                let child = self.node(child);
+                main_size += child.min(main);
+                cross_size = cross_size.max(child.min(cross));
+            }
+            let (start, end) = node.padding_along(cross);
+            cross_size += start + end;
+
+            content = match main {
+                Axis::Horizontal => Size::new(main_size, cross_size),
+                Axis::Vertical => Size::new(cross_size, main_size),
+            };
+        }
+
+        let node = self.node_mut(id);
+        for (axis, content) in [
+            (Axis::Horizontal, content.width),
+            (Axis::Vertical, content.height),
+        ] {
+            let min = match node.sizing(axis) {
+                BoxSizing::Fit | BoxSizing::Flex(_) => content,
+                BoxSizing::Fixed(size) => size,
+                BoxSizing::Percent(_) => node.max(axis),
+            };
+            node.set_min(axis, min);
+        }
+        node.constrain_aspect_ratio();
+    }
+
+    fn update_size(&mut self, id: NodeId) {
+        let node = self.node_mut(id);
+        let [width, height] =
+            [Axis::Horizontal, Axis::Vertical].map(|axis| match node.sizing(axis) {
+                BoxSizing::Fit => node.min(axis),
+                BoxSizing::Fixed(size) => size,
+                BoxSizing::Flex(_) | BoxSizing::Percent(_) => node.max(axis),
+            });
+        node.size = Size::new(width, height);
+    }
+
+    fn position_children(&mut self, id: NodeId) {
+        let node = self.node(id);
+        let Some(main) = node.main_axis() else {
+            return;
+        };
+        let cross = main.cross();
+        let position = node.position;
+        let spacing = node.spacing as f32;
+        let cross_alignment = node.cross_axis_alignment;
+
+        let (main_start, main_end) = node.padding_along(main);
+        let (cross_start, cross_end) = node.padding_along(cross);
+        let count = node.children.len();
+        let content: f32 = node
+            .children
+            .iter()
+            .map(|&c| self.node(c).size_along(main))
+            .sum();
+        let gaps = spacing * count.saturating_sub(1) as f32;
+        let free_space = node.size_along(main) - main_start - main_end - gaps - content;
+        let (offset, gap) = node.main_axis_alignment.distribute(free_space, count);
+        let cross_space = node.size_along(cross) - cross_start - cross_end;
+
+        let children = std::mem::take(&mut self.node_mut(id).children);
+        let mut main_offset = main_start + offset;
+        for &child in &children {
+            let child = self.node_mut(child);
+            let cross_offset =
+                cross_start + cross_alignment.offset(cross_space - child.size_along(cross));
+            child.position = match main {
+                Axis::Horizontal => {
+                    Position::new(position.x + main_offset, position.y + cross_offset)
+                }
+                Axis::Vertical => {
+                    Position::new(position.x + cross_offset, position.y + main_offset)
+                }
+            };
+            main_offset += child.size_along(main) + spacing + gap;
+        }
+        self.node_mut(id).children = children;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuse_free_slots() {
+        let mut tree = LayoutTree::new();
+        let first = tree.insert(Node::empty());
+        tree.remove(first).unwrap();
+        let second = tree.insert(Node::empty());
+
+        assert_eq!(tree.slots.len(), 1);
+        assert_ne!(first, second);
+        assert!(tree.get(first).is_none());
+    }
+
+    #[test]
+    fn preorder() {
+        let mut tree = LayoutTree::new();
+        let root = tree.insert(Node::vertical());
+        let row = tree.insert_child(root, Node::horizontal()).unwrap();
+        let a = tree.insert_child(row, Node::empty()).unwrap();
+        let b = tree.insert_child(root, Node::empty()).unwrap();
+
+        assert_eq!(tree.preorder(root), [root, row, a, b]);
+    }
+}
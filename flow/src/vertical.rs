@@ -0,0 +1,564 @@
+use crate::absolute::{constrain_absolute, in_flow, position_absolute};
+use crate::cache::LayoutCache;
+use crate::content::{ContentSize, fit_width, outer_height, outer_width, share_fit_space};
+use crate::snap::snap_edges;
+use crate::{
+    BoxConstraints, BoxSizing, CrossAxisAlignment, Layout, LayoutDirection, MainAxisAlignment,
+    Margin, Padding, Position, Size, SizeBounds, constrain_aspect_ratio, distribute_flex,
+    impl_alignment, impl_layout, impl_margin, impl_padding, impl_shrink, impl_size,
+};
+use ruby_core::GlobalId;
+
+/// A [`Layout`] that arranges it's children vertically.
+///
+/// # Example
+/// ```
+/// use flow::{VerticalLayout,EmptyLayout,Size,BoxSizing,Layout};
+///
+/// let mut child1 = EmptyLayout::new();
+/// let mut child2 = EmptyLayout::new();
+///
+/// child1.intrinsic_height = BoxSizing::Fixed(200.0);
+/// child2.intrinsic_height = BoxSizing::Fixed(300.0);
+///
+/// let mut layout = VerticalLayout::new();
+/// layout.push(child1);
+/// layout.push(child2);
+///
+/// flow::solve_layout(&mut layout,Size::unit(1000.0)).unwrap();
+/// assert_eq!(layout.size().height,500.0);
+/// ```
+///
+#[derive(Debug, Default)]
+pub struct VerticalLayout {
+    id: GlobalId,
+    size: Size<f32>,
+    position: Position<f32>,
+    children: Vec<Box<dyn Layout>>,
+    pub spacing: u32,
+    pub padding: Padding,
+    pub main_axis_alignment: MainAxisAlignment,
+    pub cross_axis_alignment: CrossAxisAlignment,
+    pub intrinsic_width: BoxSizing,
+    pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    /// Skip this layout and it's children when hit testing
+    pub non_interactive: bool,
+    pub margin: Margin,
+    /// How much the layout shrinks, relative to it's siblings, when it's
+    /// parent runs out of space. Defaults to zero, so layouts keep their
+    /// size unless they opt in.
+    pub shrink: f32,
+    direction: LayoutDirection,
+    scale_factor: Option<f32>,
+    constraints: BoxConstraints,
+    cache: LayoutCache,
+}
+
+impl VerticalLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn children(&self) -> &[Box<dyn Layout>] {
+        self.children.as_slice()
+    }
+
+    pub fn push(&mut self, child: impl Layout + 'static) {
+        self.mark_dirty();
+        self.children.push(Box::new(child));
+    }
+
+    /// Insert a child at `index`, shifting all the children after it.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of children.
+    pub fn insert(&mut self, index: usize, child: impl Layout + 'static) {
+        self.mark_dirty();
+        self.children.insert(index, Box::new(child));
+    }
+
+    /// Append a list of layouts
+    ///
+    /// # Example
+    /// ```
+    /// use flow::{VerticalLayout,EmptyLayout};
+    ///
+    /// let mut layout = VerticalLayout::new();
+    /// layout.append([EmptyLayout::new(),EmptyLayout::new()]);
+    ///
+    /// assert_eq!(layout.children().len(),2);
+    /// ```
+    pub fn append<I>(&mut self, children: I)
+    where
+        I: IntoIterator<Item: Layout + 'static>,
+    {
+        self.mark_dirty();
+        for child in children {
+            self.children.push(Box::new(child));
+        }
+    }
+
+    /// The height available to the children, excluding the padding
+    fn content_height(&self) -> f32 {
+        let padding = self.padding.top + self.padding.bottom;
+        self.constraints.max_height - padding as f32
+    }
+
+    /// Calculate the total height of the children with
+    /// fixed or percent `instrinsic_height`
+    fn sum_fixed_height(&self) -> f32 {
+        let mut sum = 0.0;
+        for child in self.children.iter().filter(|c| in_flow(c.as_ref())) {
+            match child.intrinsic_height() {
+                BoxSizing::Fixed(height) => sum += height,
+                BoxSizing::Percent(percent) => {
+                    sum += BoxSizing::percent_of(percent, self.content_height())
+                }
+                _ => {}
+            }
+        }
+        sum
+    }
+
+    /// Get the max height of each child that fits it's content, sharing
+    /// the `space` left by the fixed children between them. The other
+    /// children get a height of zero. The max widths of the children
+    /// must already be set.
+    fn fit_heights(&self, space: f32) -> Vec<f32> {
+        let sizes: Vec<_> = self
+            .children
+            .iter()
+            .map(|child| {
+                if in_flow(child.as_ref()) && child.intrinsic_height() == BoxSizing::Fit {
+                    let width = Some(child.constraints().max_width);
+                    (
+                        child.min_content_height(width),
+                        child.max_content_height(width),
+                    )
+                } else {
+                    (0.0, 0.0)
+                }
+            })
+            .collect();
+        share_fit_space(space, &sizes)
+    }
+
+    /// Calculate the total vertical margin of the children
+    fn total_margin(&self) -> f32 {
+        self.children
+            .iter()
+            .filter(|c| in_flow(c.as_ref()))
+            .map(|c| c.margin().vertical())
+            .sum()
+    }
+
+    /// Calculate the total space taken up by the spacing
+    /// between the children
+    fn total_spacing(&self) -> f32 {
+        let count = self.children.iter().filter(|c| in_flow(c.as_ref())).count();
+        let gaps = count.saturating_sub(1);
+        (self.spacing as usize * gaps) as f32
+    }
+
+    /// Get the content width of the layout, which is the width of the
+    /// widest child
+    fn measure_width(&self, size: ContentSize, _height: Option<f32>) -> f32 {
+        self.intrinsic_width.or_content(|| {
+            let width = self
+                .children
+                .iter()
+                .map(|child| outer_width(child.as_ref(), size, None))
+                .fold(0.0, f32::max);
+            self.bounds.clamp_width(width + self.padding.horizontal())
+        })
+    }
+
+    /// Get the content height of the layout, with the children stacked
+    /// on top of each other
+    fn measure_height(&self, size: ContentSize, width: Option<f32>) -> f32 {
+        self.intrinsic_height.or_content(|| {
+            let width = width.map(|width| width - self.padding.horizontal());
+            let height: f32 = self
+                .children
+                .iter()
+                .map(|child| outer_height(child.as_ref(), size, width))
+                .sum();
+            let height = height + self.total_spacing() + self.padding.vertical();
+            self.bounds.clamp_height(height)
+        })
+    }
+
+    /// Share the flex space between the flex children, in the
+    /// order of the children
+    fn flex_heights(&self, space: f32) -> Vec<f32> {
+        let (weights, bounds): (Vec<_>, Vec<_>) = self
+            .children
+            .iter()
+            .filter(|c| in_flow(c.as_ref()))
+            .filter_map(|c| match c.intrinsic_height() {
+                BoxSizing::Flex(flex) => {
+                    let bounds = c.bounds();
+                    Some((flex, (bounds.min_height, bounds.max_height)))
+                }
+                _ => None,
+            })
+            .unzip();
+        distribute_flex(space, &weights, &bounds)
+    }
+
+    impl_size!();
+    impl_margin!();
+    impl_shrink!();
+    impl_padding!();
+    impl_alignment!();
+}
+
+impl Layout for VerticalLayout {
+    fn solve_max_constraints(&mut self) {
+        if self.cache.skip(self.constraints) {
+            return;
+        }
+
+        for child in &mut self.children {
+            child.set_direction(self.direction);
+            child.set_scale_factor(self.scale_factor);
+        }
+
+        constrain_aspect_ratio(self);
+        let padding = self.padding.left + self.padding.right;
+        let max_width = self.constraints.max_width - padding as f32;
+
+        // The widths are solved first, since the content height of
+        // the children depends on their width
+        for child in &mut self.children {
+            // Absolutely positioned children are constrained once
+            // the size of this layout is known
+            if !in_flow(child.as_ref()) {
+                continue;
+            }
+
+            match child.intrinsic_width() {
+                BoxSizing::Fit => {
+                    let available = max_width - child.margin().horizontal();
+                    child.set_max_width(fit_width(child.as_ref(), available, None));
+                }
+                BoxSizing::Flex(_) => {
+                    child.set_max_width(max_width - child.margin().horizontal());
+                }
+                BoxSizing::Fixed(width) => child.set_max_width(width),
+                BoxSizing::Percent(percent) => {
+                    child.set_max_width(BoxSizing::percent_of(percent, max_width));
+                }
+            }
+        }
+
+        let total_height = self.sum_fixed_height() + self.total_spacing() + self.total_margin();
+        let content_height = self.content_height();
+        let fit_heights = self.fit_heights((content_height - total_height).max(0.0));
+        let fit_height: f32 = fit_heights.iter().sum();
+        // The fixed and fit children may take up more than the available
+        // space, in which case the flex children get nothing and the
+        // layout overflows
+        let max_height = (content_height - total_height - fit_height).max(0.0);
+        let mut flex_sizes = self.flex_heights(max_height).into_iter();
+
+        for (child, fit_height) in self.children.iter_mut().zip(fit_heights) {
+            if !in_flow(child.as_ref()) {
+                continue;
+            }
+
+            match child.intrinsic_height() {
+                BoxSizing::Fit => child.set_max_height(fit_height),
+                BoxSizing::Flex(_) => {
+                    let height = flex_sizes.next().unwrap_or_default();
+                    child.set_max_height(height);
+                }
+                BoxSizing::Fixed(height) => child.set_max_height(height),
+                BoxSizing::Percent(percent) => {
+                    child.set_max_height(BoxSizing::percent_of(percent, content_height));
+                }
+            }
+
+            child.solve_max_constraints();
+        }
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if !self.cache.recomputed() {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        let mut min_height = 0.0;
+        let mut min_width: f32 = 0.0;
+
+        for child in &mut self.children {
+            let (width, height) = child.solve_min_constraints();
+            if !in_flow(child.as_ref()) {
+                continue;
+            }
+            let margin = child.margin();
+            min_height += height + margin.vertical();
+            min_width = min_width.max(width + margin.horizontal());
+        }
+
+        let padding = self.padding.top + self.padding.bottom;
+        min_height += self.total_spacing() + padding as f32;
+        let padding = self.padding.left + self.padding.right;
+        min_width += padding as f32;
+
+        match self.intrinsic_width {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_width(min_width),
+            BoxSizing::Fixed(width) => self.set_min_width(width),
+            BoxSizing::Percent(_) => self.set_min_width(self.constraints.max_width),
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_height(min_height),
+            BoxSizing::Fixed(height) => self.set_min_height(height),
+            BoxSizing::Percent(_) => self.set_min_height(self.constraints.max_height),
+        }
+
+        constrain_aspect_ratio(self);
+        (self.constraints.min_width, self.constraints.min_height)
+    }
+
+    fn update_size(&mut self) {
+        if !self.cache.begin_update() {
+            return;
+        }
+
+        match self.intrinsic_width {
+            BoxSizing::Fit => self.size.width = self.constraints.min_width,
+            BoxSizing::Fixed(width) => self.size.width = width,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.width = self.constraints.max_width
+            }
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit => self.size.height = self.constraints.min_height,
+            BoxSizing::Fixed(height) => self.size.height = height,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.height = self.constraints.max_height
+            }
+        }
+
+        for child in &mut self.children {
+            constrain_absolute(child.as_mut(), self.size);
+            child.update_size();
+        }
+    }
+
+    fn position_children(&mut self) {
+        if self.cache.skip_position(self.position) {
+            return;
+        }
+
+        let padding = self.padding.top + self.padding.bottom;
+        let children = self.children.iter().filter(|c| in_flow(c.as_ref()));
+        let count = children.clone().count();
+        let content: f32 = children
+            .map(|c| c.size().height + c.margin().vertical())
+            .sum();
+        let free_space = self.size.height - padding as f32 - self.total_spacing() - content;
+        let (offset, gap) = self.main_axis_alignment.distribute(free_space, count);
+
+        let cross_padding = self.padding.left + self.padding.right;
+        let cross_space = self.size.width - cross_padding as f32;
+
+        let mut y = self.position.y + self.padding.top as f32 + offset;
+        for child in &mut self.children {
+            if !in_flow(child.as_ref()) {
+                position_absolute(child.as_mut(), self.position, self.size);
+                continue;
+            }
+
+            let margin = child.margin();
+            let free_space = cross_space - child.size().width - margin.horizontal();
+            let x = self.position.x
+                + self.padding.left as f32
+                + margin.left as f32
+                + self.cross_axis_alignment.offset(free_space);
+
+            let x = self
+                .direction
+                .place(x, child.size().width, self.position.x, self.size.width);
+
+            y += margin.top as f32;
+            child.set_position(Position { x, y });
+            child.position_children();
+
+            y += child.size().height + margin.bottom as f32;
+            y += self.spacing as f32 + gap;
+        }
+    }
+
+    fn snap_to_pixels(&mut self) {
+        snap_edges(self);
+        for child in &mut self.children {
+            child.snap_to_pixels();
+        }
+    }
+
+    fn min_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Min, height)
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Max, height)
+    }
+
+    fn min_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Min, width)
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Max, width)
+    }
+
+    fn children(&self) -> Vec<&dyn Layout> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Layout> {
+        self.mark_dirty();
+        self.children
+            .iter_mut()
+            .map(|c| c.as_mut() as &mut dyn Layout)
+            .collect()
+    }
+
+    fn remove_child(&mut self, id: GlobalId) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        Some(self.children.remove(index))
+    }
+
+    fn replace_child(&mut self, id: GlobalId, child: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        Some(std::mem::replace(&mut self.children[index], child))
+    }
+
+    impl_layout!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EmptyLayout, HorizontalLayout};
+
+    #[test]
+    fn sum_fixed_height() {
+        let child1 = EmptyLayout::new().fixed(200.0, 300.0);
+        let child2 = EmptyLayout::new().fixed(50.0, 670.0);
+
+        let mut layout = VerticalLayout::new();
+        layout.append([child1, child2]);
+        let total_height = layout.sum_fixed_height();
+
+        assert_eq!(total_height, 970.0);
+    }
+
+    #[test]
+    fn subtract_fixed_height_from_flex() {
+        let child1 = EmptyLayout::new().fixed_height(200.0);
+        let child2 = EmptyLayout::new().fixed_height(50.0);
+        let child3 = EmptyLayout::new().fill();
+
+        let mut layout = VerticalLayout::new();
+        layout.push(child1);
+        layout.push(child2);
+        layout.push(child3);
+        layout.set_max_height(500.0);
+        layout.solve_max_constraints();
+
+        let child3 = &layout.children[2];
+
+        assert_eq!(child3.constraints().max_height, 250.0);
+    }
+
+    #[test]
+    fn fixed_max_constraints() {
+        let child1 = EmptyLayout::new().fixed(200.0, 300.0);
+        let child2 = EmptyLayout::new().fixed(50.0, 670.0);
+
+        let mut layout = VerticalLayout::new();
+        layout.push(child1);
+        layout.push(child2);
+        layout.solve_max_constraints();
+
+        let child1 = &layout.children[0];
+        let child2 = &layout.children[1];
+
+        assert_eq!(child1.constraints().max_width, 200.0);
+        assert_eq!(child1.constraints().max_height, 300.0);
+        assert_eq!(child2.constraints().max_width, 50.0);
+        assert_eq!(child2.constraints().max_height, 670.0);
+    }
+
+    #[test]
+    fn fill_max_constraints() {
+        let child1 = EmptyLayout::new().fill();
+        let child2 = EmptyLayout::new().fill();
+
+        let mut layout = VerticalLayout::new();
+        layout.set_max_width(1000.0);
+        layout.set_max_height(1000.0);
+        layout.push(child1);
+        layout.push(child2);
+        layout.solve_max_constraints();
+
+        let child1 = &layout.children[0];
+        let child2 = &layout.children[1];
+
+        assert_eq!(child1.constraints().max_height, 500.0);
+        assert_eq!(child2.constraints().max_height, 500.0);
+        assert_eq!(child1.constraints().max_width, 1000.0);
+        assert_eq!(child2.constraints().max_width, 1000.0);
+    }
+
+    #[test]
+    fn fit_max_constraints() {
+        let mut child1 = HorizontalLayout::new();
+        let mut child2 = HorizontalLayout::new();
+        child1.push(EmptyLayout::new().fixed(150.0, 50.0));
+        child2.push(EmptyLayout::new().fixed(300.0, 349.0));
+
+        let mut layout = VerticalLayout::new();
+        layout.set_max_width(1000.0);
+        layout.set_max_height(1000.0);
+        layout.push(child1);
+        layout.push(child2);
+        layout.solve_max_constraints();
+
+        let child1 = &layout.children[0];
+        let child2 = &layout.children[1];
+
+        assert_eq!(child1.constraints().max_width, 150.0);
+        assert_eq!(child2.constraints().max_width, 300.0);
+        assert_eq!(child1.constraints().max_height, 50.0);
+        assert_eq!(child2.constraints().max_height, 349.0);
+    }
+
+    #[test]
+    fn update_children_size() {
+        let mut child1 = EmptyLayout::new();
+        let mut child2 = EmptyLayout::new();
+        child1.intrinsic_width = BoxSizing::Fixed(250.0);
+        child2.intrinsic_height = BoxSizing::Fixed(90.0);
+
+        let mut layout = VerticalLayout::new();
+        layout.push(child1);
+        layout.push(child2);
+
+        layout.update_size();
+
+        let child1 = &layout.children[0];
+        let child2 = &layout.children[1];
+
+        assert_eq!(child1.size().width, 250.0);
+        assert_eq!(child2.size().height, 90.0);
+    }
+}
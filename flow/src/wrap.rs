@@ -0,0 +1,495 @@
+use crate::absolute::{constrain_absolute, in_flow, position_absolute};
+use crate::cache::LayoutCache;
+use crate::content::{ContentSize, fit_height, fit_width, outer_height, outer_width};
+use crate::snap::snap_edges;
+use crate::{
+    BoxConstraints, BoxSizing, CrossAxisAlignment, Layout, LayoutDirection, MainAxisAlignment,
+    Margin, Padding, Position, Size, SizeBounds, constrain_aspect_ratio, impl_alignment,
+    impl_layout, impl_margin, impl_padding, impl_shrink, impl_size,
+};
+use ruby_core::GlobalId;
+
+/// A [`Layout`] that arranges it's children horizontally, breaking them
+/// onto a new run when they run out of width, similar to `flex-wrap`
+/// in CSS.
+///
+/// Each run is as tall as it's tallest child. Children with a flex width
+/// take up an entire run and children with a flex height are stretched
+/// to the height of their run.
+///
+/// # Example
+/// ```
+/// use flow::{WrapLayout,EmptyLayout,Size,Layout};
+///
+/// let mut layout = WrapLayout::new().fixed_width(250.0);
+/// layout.append([
+///     EmptyLayout::new().fixed(100.0, 20.0),
+///     EmptyLayout::new().fixed(100.0, 20.0),
+///     EmptyLayout::new().fixed(100.0, 30.0),
+/// ]);
+///
+/// flow::solve_layout(&mut layout,Size::unit(1000.0)).unwrap();
+/// assert_eq!(layout.size().height,50.0);
+/// ```
+#[derive(Debug, Default)]
+pub struct WrapLayout {
+    id: GlobalId,
+    size: Size<f32>,
+    position: Position<f32>,
+    children: Vec<Box<dyn Layout>>,
+    /// The space between children in the same run
+    pub spacing: u32,
+    /// The space between each run
+    pub run_spacing: u32,
+    pub padding: Padding,
+    pub main_axis_alignment: MainAxisAlignment,
+    pub cross_axis_alignment: CrossAxisAlignment,
+    pub intrinsic_width: BoxSizing,
+    pub intrinsic_height: BoxSizing,
+    pub bounds: SizeBounds,
+    /// Skip this layout and it's children when hit testing
+    pub non_interactive: bool,
+    pub margin: Margin,
+    /// How much the layout shrinks, relative to it's siblings, when it's
+    /// parent runs out of space. Defaults to zero, so layouts keep their
+    /// size unless they opt in.
+    pub shrink: f32,
+    direction: LayoutDirection,
+    scale_factor: Option<f32>,
+    constraints: BoxConstraints,
+    cache: LayoutCache,
+}
+
+/// A single line of children in a [`WrapLayout`].
+#[derive(Debug, Clone, PartialEq)]
+struct Run {
+    /// The index of each child in the run
+    children: Vec<usize>,
+    /// The total width of the children, including spacing
+    width: f32,
+    /// The height of the tallest child
+    height: f32,
+}
+
+impl WrapLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn children(&self) -> &[Box<dyn Layout>] {
+        self.children.as_slice()
+    }
+
+    pub fn push(&mut self, child: impl Layout + 'static) {
+        self.mark_dirty();
+        self.children.push(Box::new(child));
+    }
+
+    /// Insert a child at `index`, shifting all the children after it.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of children.
+    pub fn insert(&mut self, index: usize, child: impl Layout + 'static) {
+        self.mark_dirty();
+        self.children.insert(index, Box::new(child));
+    }
+
+    /// Append a list of layouts
+    pub fn append<I>(&mut self, children: I)
+    where
+        I: IntoIterator<Item: Layout + 'static>,
+    {
+        self.mark_dirty();
+        for child in children {
+            self.children.push(Box::new(child));
+        }
+    }
+
+    /// The width available to each run
+    fn available_width(&self) -> f32 {
+        let padding = self.padding.left + self.padding.right;
+        self.constraints.max_width - padding as f32
+    }
+
+    /// Break the children into runs no wider than `max_width`, given the
+    /// index and size of each child in the normal flow.
+    fn runs(&self, sizes: &[(usize, Size<f32>)], max_width: f32) -> Vec<Run> {
+        let spacing = self.spacing as f32;
+        let mut runs: Vec<Run> = vec![];
+
+        for &(index, size) in sizes {
+            match runs.last_mut() {
+                Some(run) if run.width + spacing + size.width <= max_width => {
+                    run.children.push(index);
+                    run.width += spacing + size.width;
+                    run.height = run.height.max(size.height);
+                }
+                _ => runs.push(Run {
+                    children: vec![index],
+                    width: size.width,
+                    height: size.height,
+                }),
+            }
+        }
+
+        runs
+    }
+
+    /// Calculate the total height of the runs, including the
+    /// run spacing
+    fn runs_height(&self, runs: &[Run]) -> f32 {
+        let gaps = runs.len().saturating_sub(1) as f32;
+        let height: f32 = runs.iter().map(|r| r.height).sum();
+        height + self.run_spacing as f32 * gaps
+    }
+
+    /// Get the content width of the layout. At it's narrowest every child
+    /// is on it's own run, with unlimited space they all share one run.
+    fn measure_width(&self, size: ContentSize, _height: Option<f32>) -> f32 {
+        self.intrinsic_width.or_content(|| {
+            let children = self.children.iter().filter(|c| in_flow(c.as_ref()));
+            let gaps = children.clone().count().saturating_sub(1);
+            let widths = children.map(|child| outer_width(child.as_ref(), size, None));
+            let width = match size {
+                ContentSize::Min => widths.fold(0.0, f32::max),
+                ContentSize::Max => widths.sum::<f32>() + (self.spacing as usize * gaps) as f32,
+            };
+            self.bounds.clamp_width(width + self.padding.horizontal())
+        })
+    }
+
+    /// Get the content height of the layout, with the children wrapped
+    /// to the width if it's known.
+    fn measure_height(&self, size: ContentSize, width: Option<f32>) -> f32 {
+        self.intrinsic_height.or_content(|| {
+            let max_width = width.map_or(f32::INFINITY, |width| width - self.padding.horizontal());
+            let sizes: Vec<_> = self
+                .children
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| in_flow(c.as_ref()))
+                .map(|(index, child)| {
+                    let child = child.as_ref();
+                    let width = outer_width(child, ContentSize::Max, None).min(max_width);
+                    let height = outer_height(child, size, Some(width));
+                    (index, Size::new(width, height))
+                })
+                .collect();
+            let runs = self.runs(&sizes, max_width);
+            let height = self.runs_height(&runs) + self.padding.vertical();
+            self.bounds.clamp_height(height)
+        })
+    }
+
+    impl_size!();
+    impl_margin!();
+    impl_shrink!();
+    impl_padding!();
+    impl_alignment!();
+}
+
+impl Layout for WrapLayout {
+    fn solve_max_constraints(&mut self) {
+        if self.cache.skip(self.constraints) {
+            return;
+        }
+
+        for child in &mut self.children {
+            child.set_direction(self.direction);
+            child.set_scale_factor(self.scale_factor);
+        }
+
+        constrain_aspect_ratio(self);
+        let max_width = self.available_width();
+        let padding = self.padding.top + self.padding.bottom;
+        let max_height = self.constraints.max_height - padding as f32;
+
+        for child in &mut self.children {
+            // Absolutely positioned children are constrained once
+            // the size of this layout is known
+            if !in_flow(child.as_ref()) {
+                continue;
+            }
+
+            let margin = child.margin();
+            match child.intrinsic_width() {
+                BoxSizing::Fit => {
+                    let available = max_width - margin.horizontal();
+                    child.set_max_width(fit_width(child.as_ref(), available, None));
+                }
+                BoxSizing::Flex(_) => child.set_max_width(max_width - margin.horizontal()),
+                BoxSizing::Fixed(width) => child.set_max_width(width),
+                BoxSizing::Percent(percent) => {
+                    child.set_max_width(BoxSizing::percent_of(percent, max_width));
+                }
+            }
+
+            match child.intrinsic_height() {
+                BoxSizing::Fit => {
+                    let available = max_height - margin.vertical();
+                    let width = Some(child.constraints().max_width);
+                    child.set_max_height(fit_height(child.as_ref(), available, width));
+                }
+                BoxSizing::Flex(_) => child.set_max_height(max_height - margin.vertical()),
+                BoxSizing::Fixed(height) => child.set_max_height(height),
+                BoxSizing::Percent(percent) => {
+                    child.set_max_height(BoxSizing::percent_of(percent, max_height));
+                }
+            }
+
+            child.solve_max_constraints();
+        }
+    }
+
+    fn solve_min_constraints(&mut self) -> (f32, f32) {
+        if !self.cache.recomputed() {
+            return (self.constraints.min_width, self.constraints.min_height);
+        }
+
+        let mut sizes = vec![];
+        for (index, child) in self.children.iter_mut().enumerate() {
+            let (min_width, min_height) = child.solve_min_constraints();
+            if !in_flow(child.as_ref()) {
+                continue;
+            }
+
+            let width = match child.intrinsic_width() {
+                BoxSizing::Flex(_) => child.constraints().max_width,
+                _ => min_width,
+            };
+            let margin = child.margin();
+            let size = Size::new(width + margin.horizontal(), min_height + margin.vertical());
+            sizes.push((index, size));
+        }
+
+        let runs = self.runs(&sizes, self.available_width());
+
+        // Stretch the flex children to the height of their run
+        for run in &runs {
+            for &index in &run.children {
+                let child = &mut self.children[index];
+                if let BoxSizing::Flex(_) = child.intrinsic_height() {
+                    child.set_max_height(run.height - child.margin().vertical());
+                    child.solve_max_constraints();
+                }
+            }
+        }
+
+        let width = runs.iter().map(|r| r.width).fold(0.0, f32::max);
+        let padding = self.padding.left + self.padding.right;
+        let min_width = width + padding as f32;
+        let padding = self.padding.top + self.padding.bottom;
+        let min_height = self.runs_height(&runs) + padding as f32;
+
+        match self.intrinsic_width {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_width(min_width),
+            BoxSizing::Fixed(width) => self.set_min_width(width),
+            BoxSizing::Percent(_) => self.set_min_width(self.constraints.max_width),
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit | BoxSizing::Flex(_) => self.set_min_height(min_height),
+            BoxSizing::Fixed(height) => self.set_min_height(height),
+            BoxSizing::Percent(_) => self.set_min_height(self.constraints.max_height),
+        }
+
+        constrain_aspect_ratio(self);
+        (self.constraints.min_width, self.constraints.min_height)
+    }
+
+    fn update_size(&mut self) {
+        if !self.cache.begin_update() {
+            return;
+        }
+
+        match self.intrinsic_width {
+            BoxSizing::Fit => self.size.width = self.constraints.min_width,
+            BoxSizing::Fixed(width) => self.size.width = width,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.width = self.constraints.max_width
+            }
+        }
+
+        match self.intrinsic_height {
+            BoxSizing::Fit => self.size.height = self.constraints.min_height,
+            BoxSizing::Fixed(height) => self.size.height = height,
+            BoxSizing::Flex(_) | BoxSizing::Percent(_) => {
+                self.size.height = self.constraints.max_height
+            }
+        }
+
+        for child in &mut self.children {
+            constrain_absolute(child.as_mut(), self.size);
+            child.update_size();
+        }
+    }
+
+    fn position_children(&mut self) {
+        if self.cache.skip_position(self.position) {
+            return;
+        }
+
+        let sizes: Vec<_> = self
+            .children
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| in_flow(c.as_ref()))
+            .map(|(index, c)| {
+                let margin = c.margin();
+                let size = c.size();
+                let width = size.width + margin.horizontal();
+                (index, Size::new(width, size.height + margin.vertical()))
+            })
+            .collect();
+        let runs = self.runs(&sizes, self.available_width());
+
+        let padding = self.padding.left + self.padding.right;
+        let content_width = self.size.width - padding as f32;
+        let mut y = self.position.y + self.padding.top as f32;
+
+        for run in runs {
+            let count = run.children.len();
+            let (offset, gap) = self
+                .main_axis_alignment
+                .distribute(content_width - run.width, count);
+            let mut x = self.position.x + self.padding.left as f32 + offset;
+
+            for &index in &run.children {
+                let child = &mut self.children[index];
+                let margin = child.margin();
+                let free_space = run.height - child.size().height - margin.vertical();
+                let y = y + margin.top as f32 + self.cross_axis_alignment.offset(free_space);
+
+                x += margin.left as f32;
+                let width = child.size().width;
+                let child_x = self
+                    .direction
+                    .place(x, width, self.position.x, self.size.width);
+                child.set_position(Position { x: child_x, y });
+                child.position_children();
+
+                x += width + margin.right as f32;
+                x += self.spacing as f32 + gap;
+            }
+
+            y += run.height + self.run_spacing as f32;
+        }
+
+        for child in &mut self.children {
+            position_absolute(child.as_mut(), self.position, self.size);
+        }
+    }
+
+    fn snap_to_pixels(&mut self) {
+        snap_edges(self);
+        for child in &mut self.children {
+            child.snap_to_pixels();
+        }
+    }
+
+    fn min_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Min, height)
+    }
+
+    fn max_content_width(&self, height: Option<f32>) -> f32 {
+        self.measure_width(ContentSize::Max, height)
+    }
+
+    fn min_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Min, width)
+    }
+
+    fn max_content_height(&self, width: Option<f32>) -> f32 {
+        self.measure_height(ContentSize::Max, width)
+    }
+
+    fn children(&self) -> Vec<&dyn Layout> {
+        self.children.iter().map(|c| c.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Layout> {
+        self.mark_dirty();
+        self.children
+            .iter_mut()
+            .map(|c| c.as_mut() as &mut dyn Layout)
+            .collect()
+    }
+
+    fn remove_child(&mut self, id: GlobalId) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        Some(self.children.remove(index))
+    }
+
+    fn replace_child(&mut self, id: GlobalId, child: Box<dyn Layout>) -> Option<Box<dyn Layout>> {
+        let index = self.children.iter().position(|c| c.id() == id)?;
+        self.mark_dirty();
+        Some(std::mem::replace(&mut self.children[index], child))
+    }
+
+    impl_layout!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EmptyLayout;
+
+    #[test]
+    fn break_into_runs() {
+        let mut layout = WrapLayout::new();
+        layout.spacing = 10;
+        layout.set_max_width(300.0);
+        let sizes = [
+            (0, Size::new(100.0, 20.0)),
+            (1, Size::new(100.0, 40.0)),
+            (2, Size::new(100.0, 30.0)),
+            (3, Size::new(250.0, 10.0)),
+        ];
+        let runs = layout.runs(&sizes, layout.available_width());
+
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0].children, [0, 1]);
+        assert_eq!(runs[0].width, 210.0);
+        assert_eq!(runs[0].height, 40.0);
+        assert_eq!(runs[1].children, [2]);
+        assert_eq!(runs[2].children, [3]);
+    }
+
+    #[test]
+    fn oversized_child_gets_own_run() {
+        let mut layout = WrapLayout::new();
+        layout.set_max_width(100.0);
+        let sizes = [(0, Size::new(150.0, 20.0)), (1, Size::new(50.0, 20.0))];
+        let runs = layout.runs(&sizes, layout.available_width());
+
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].width, 150.0);
+    }
+
+    #[test]
+    fn runs_height_includes_run_spacing() {
+        let mut layout = WrapLayout::new();
+        layout.run_spacing = 8;
+        layout.set_max_width(100.0);
+        let sizes = [(0, Size::new(100.0, 20.0)), (1, Size::new(100.0, 30.0))];
+        let runs = layout.runs(&sizes, layout.available_width());
+
+        assert_eq!(layout.runs_height(&runs), 58.0);
+    }
+
+    #[test]
+    fn stretch_flex_height_to_run() {
+        let mut layout = WrapLayout::new();
+        layout.append([
+            EmptyLayout::new().fixed(50.0, 40.0),
+            EmptyLayout::new().fixed_width(50.0).fill_height(),
+        ]);
+        layout.set_max_width(500.0);
+        layout.set_max_height(500.0);
+        layout.solve_max_constraints();
+        layout.solve_min_constraints();
+
+        assert_eq!(layout.children[1].constraints().max_height, 40.0);
+    }
+}
@@ -0,0 +1,163 @@
+use flow::{
+    AbsoluteLayout, BlockLayout, BoxSizing, EmptyLayout, GridLayout, HorizontalLayout, Layout,
+    MeasuredLayout, Position, Size, StackLayout, VerticalLayout, WrapLayout, solve_layout,
+};
+
+#[test]
+fn ignored_in_min_constraints() {
+    let overlay = AbsoluteLayout::new(EmptyLayout::new().fixed(500.0, 500.0));
+
+    let mut root = HorizontalLayout::new();
+    root.spacing = 10;
+    root.push(EmptyLayout::new().fixed(100.0, 40.0));
+    root.push(overlay);
+    root.push(EmptyLayout::new().fixed(50.0, 20.0));
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(root.size(), Size::new(160.0, 40.0));
+    assert_eq!(root.children()[1].size(), Size::unit(500.0));
+    assert_eq!(root.children()[2].position().x, 110.0);
+}
+
+#[test]
+fn ignored_in_flex_distribution() {
+    let overlay = AbsoluteLayout::new(EmptyLayout::new().fill_width().fixed_height(10.0));
+
+    let mut root = HorizontalLayout::new().fill();
+    root.push(EmptyLayout::new().fill());
+    root.push(overlay);
+    root.push(EmptyLayout::new().fill());
+    solve_layout(&mut root, Size::new(400.0, 100.0)).unwrap();
+
+    let children = root.children();
+    assert_eq!(children[0].size().width, 200.0);
+    assert_eq!(children[2].size().width, 200.0);
+    assert_eq!(children[1].size().width, 400.0);
+}
+
+#[test]
+fn flex_fills_parent_minus_insets() {
+    let overlay = AbsoluteLayout::new(EmptyLayout::new().fill())
+        .left(10.0)
+        .right(10.0)
+        .top(20.0)
+        .bottom(30.0);
+
+    let mut root = VerticalLayout::new().fixed(300.0, 200.0);
+    root.push(overlay);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let overlay = &root.children()[0];
+    assert_eq!(overlay.size(), Size::new(280.0, 150.0));
+    assert_eq!(overlay.position(), Position::new(10.0, 20.0));
+}
+
+#[test]
+fn insets_relative_to_parent() {
+    let fab = AbsoluteLayout::new(EmptyLayout::new().fixed(40.0, 40.0))
+        .right(16.0)
+        .bottom(16.0);
+
+    let mut inner = VerticalLayout::new().fixed(200.0, 300.0);
+    inner.push(fab);
+
+    let mut root = HorizontalLayout::new().padding_left(50).padding_top(25);
+    root.push(inner);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let inner = &root.children()[0];
+    assert_eq!(inner.position(), Position::new(50.0, 25.0));
+    assert_eq!(root.size(), Size::new(250.0, 325.0));
+}
+
+#[test]
+fn nested_absolute_position() {
+    let mut tooltip = HorizontalLayout::new();
+    tooltip.push(EmptyLayout::new().fixed(30.0, 10.0));
+    let tooltip = AbsoluteLayout::new(tooltip).left(5.0).bottom(0.0);
+
+    let mut root = StackLayout::new().fixed(100.0, 100.0);
+    root.push(tooltip);
+    root.set_position(Position::new(10.0, 10.0));
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(root.children()[0].position(), Position::new(15.0, 100.0));
+}
+
+#[test]
+fn block_child() {
+    let child = AbsoluteLayout::new(EmptyLayout::new().fixed(20.0, 20.0)).top(5.0);
+    let mut root = BlockLayout::new(child).padding_left(10).padding_right(10);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(root.size(), Size::new(20.0, 0.0));
+    assert_eq!(root.child().position(), Position::new(0.0, 5.0));
+    assert_eq!(root.child().size(), Size::unit(20.0));
+}
+
+#[test]
+fn wrap_child() {
+    let mut root = WrapLayout::new().fixed_width(100.0);
+    root.push(EmptyLayout::new().fixed(60.0, 20.0));
+    root.push(AbsoluteLayout::new(EmptyLayout::new().fixed(80.0, 80.0)).right(0.0));
+    root.push(EmptyLayout::new().fixed(30.0, 20.0));
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let children = root.children();
+    assert_eq!(root.size().height, 20.0);
+    assert_eq!(children[1].position(), Position::new(20.0, 0.0));
+    assert_eq!(children[2].position(), Position::new(60.0, 0.0));
+}
+
+#[test]
+fn grid_child() {
+    let mut root = GridLayout::new().columns([BoxSizing::Fixed(50.0), BoxSizing::Fixed(50.0)]);
+    root.push(EmptyLayout::new().fixed(50.0, 20.0));
+    root.push(AbsoluteLayout::new(EmptyLayout::new().fill()));
+    root.push(EmptyLayout::new().fixed(50.0, 30.0));
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let children = root.children();
+    assert_eq!(root.size(), Size::new(100.0, 30.0));
+    assert_eq!(children[1].size(), Size::new(100.0, 30.0));
+    assert_eq!(children[2].position(), Position::new(50.0, 0.0));
+}
+
+#[test]
+fn min_constraints_use_parent_size() {
+    // 350 pixels of text, wrapped to fit the max width
+    let text = MeasuredLayout::new(|constraints| {
+        let lines = (350.0 / constraints.max_width).ceil();
+        Size::new(constraints.max_width.min(350.0), lines * 20.0)
+    })
+    .fill_width();
+    let mut popup = VerticalLayout::new().fill_width();
+    popup.push(text);
+
+    let mut root = HorizontalLayout::new().fixed(200.0, 100.0);
+    root.push(AbsoluteLayout::new(popup).left(0.0));
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(root.children()[0].size(), Size::new(200.0, 40.0));
+}
+
+#[test]
+fn wrapper_is_visible_in_tree() {
+    let child = EmptyLayout::new().fixed(20.0, 20.0);
+    let child_id = child.id();
+    let overlay = AbsoluteLayout::new(child);
+    let overlay_id = overlay.id();
+    assert_ne!(overlay_id, child_id);
+
+    let mut root = HorizontalLayout::new();
+    root.push(overlay);
+    assert_eq!(root.find(overlay_id).unwrap().children()[0].id(), child_id);
+    assert_eq!(root.find(child_id).unwrap().id(), child_id);
+
+    let replacement = EmptyLayout::new();
+    let replacement_id = replacement.id();
+    let old = root.replace(child_id, Box::new(replacement)).unwrap();
+    assert_eq!(old.id(), child_id);
+    assert_eq!(root.children()[0].children()[0].id(), replacement_id);
+    assert_eq!(root.children()[0].id(), overlay_id);
+}
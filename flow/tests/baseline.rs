@@ -0,0 +1,103 @@
+use flow::{
+    CrossAxisAlignment, EmptyLayout, HorizontalLayout, Layout, MeasuredLayout, Size,
+    VerticalLayout, solve_layout,
+};
+
+/// A line of text with a fixed height and baseline
+fn text(height: f32, baseline: f32) -> MeasuredLayout {
+    MeasuredLayout::new(move |_| Size::new(50.0, height)).measure_baseline(move |_| baseline)
+}
+
+fn row() -> HorizontalLayout {
+    HorizontalLayout::new().cross_axis_alignment(CrossAxisAlignment::Baseline)
+}
+
+#[test]
+fn measured_baseline() {
+    let mut layout = MeasuredLayout::new(|_| Size::new(50.0, 20.0))
+        .measure_baseline(|size| size.height * 0.8)
+        .fixed_height(40.0);
+    assert_eq!(layout.baseline(), None);
+
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    assert_eq!(layout.baseline(), Some(32.0));
+    assert_eq!(EmptyLayout::new().baseline(), None);
+}
+
+#[test]
+fn align_by_baseline() {
+    let mut layout = row();
+    layout.push(text(20.0, 16.0));
+    layout.push(text(40.0, 28.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size().height, 40.0);
+    assert_eq!(layout.children()[0].position().y, 12.0);
+    assert_eq!(layout.children()[1].position().y, 0.0);
+    assert_eq!(layout.baseline(), Some(28.0));
+}
+
+#[test]
+fn grow_to_fit_ascent_and_descent() {
+    let mut layout = row().padding_top(5);
+    layout.push(text(20.0, 18.0));
+    layout.push(text(30.0, 10.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    // An ascent of 18 and descent of 20
+    assert_eq!(layout.size().height, 43.0);
+    assert_eq!(layout.children()[0].position().y, 5.0);
+    assert_eq!(layout.children()[1].position().y, 13.0);
+}
+
+#[test]
+fn no_baseline_aligns_bottom_edge() {
+    let mut layout = row();
+    layout.push(EmptyLayout::new().fixed(20.0, 30.0));
+    layout.push(text(20.0, 16.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size().height, 34.0);
+    assert_eq!(layout.children()[1].position().y, 14.0);
+}
+
+#[test]
+fn baseline_with_margin() {
+    let mut layout = row();
+    layout.push(text(20.0, 16.0).margin_top(10));
+    layout.push(text(20.0, 16.0).margin_bottom(6));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size().height, 36.0);
+    assert_eq!(layout.children()[0].position().y, 10.0);
+    assert_eq!(layout.children()[1].position().y, 10.0);
+}
+
+#[test]
+fn nested_row_baseline() {
+    let mut inner = row().padding_top(4);
+    inner.push(text(20.0, 16.0));
+    inner.push(text(30.0, 24.0));
+
+    let mut layout = row();
+    layout.push(inner);
+    layout.push(text(40.0, 32.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let inner = &layout.children()[0];
+    assert_eq!(inner.baseline(), Some(28.0));
+    assert_eq!(inner.position().y, 4.0);
+    assert_eq!(inner.children()[0].position().y, 16.0);
+}
+
+#[test]
+fn vertical_treats_baseline_as_start() {
+    let mut layout = VerticalLayout::new()
+        .fixed_width(200.0)
+        .cross_axis_alignment(CrossAxisAlignment::Baseline);
+    layout.push(text(20.0, 16.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].position().x, 0.0);
+    assert_eq!(layout.baseline(), None);
+}
@@ -1,13 +1,16 @@
-use flow::{BlockLayout, BoxSizing, EmptyLayout, Layout, Size, solve_layout};
+use flow::{
+    BlockLayout, BoxSizing, CrossAxisAlignment, EmptyLayout, Layout, MainAxisAlignment, Position,
+    Size, solve_layout,
+};
 
 #[test]
 fn fill_window() {
     let child = EmptyLayout::new();
     let mut root = BlockLayout::new(child);
-    root.intrinsic_height = BoxSizing::Flex(1);
-    root.intrinsic_width = BoxSizing::Flex(1);
+    root.intrinsic_height = BoxSizing::Flex(1.0);
+    root.intrinsic_width = BoxSizing::Flex(1.0);
 
-    solve_layout(&mut root, Size::unit(500.0));
+    solve_layout(&mut root, Size::unit(500.0)).unwrap();
     assert_eq!(root.size(), Size::unit(500.0));
 }
 
@@ -19,21 +22,21 @@ fn fit_child() {
 
     let mut root = BlockLayout::new(child);
 
-    solve_layout(&mut root, Size::unit(500.0));
+    solve_layout(&mut root, Size::unit(500.0)).unwrap();
     assert_eq!(root.size(), Size::new(24.0, 230.0));
 }
 
 #[test]
 fn fill_block_layout() {
     let mut child = EmptyLayout::new();
-    child.intrinsic_width = BoxSizing::Flex(1);
-    child.intrinsic_height = BoxSizing::Flex(1);
+    child.intrinsic_width = BoxSizing::Flex(1.0);
+    child.intrinsic_height = BoxSizing::Flex(1.0);
 
     let mut root = BlockLayout::new(child);
-    root.intrinsic_width = BoxSizing::Flex(1);
-    root.intrinsic_height = BoxSizing::Flex(1);
+    root.intrinsic_width = BoxSizing::Flex(1.0);
+    root.intrinsic_height = BoxSizing::Flex(1.0);
 
-    solve_layout(&mut root, Size::unit(1000.0));
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
     let child = root.child();
     assert_eq!(child.size(), Size::unit(1000.0));
 }
@@ -41,19 +44,19 @@ fn fill_block_layout() {
 #[test]
 fn padding_in_max_constraints() {
     let mut child = EmptyLayout::new();
-    child.intrinsic_width = BoxSizing::Flex(1);
-    child.intrinsic_height = BoxSizing::Flex(1);
+    child.intrinsic_width = BoxSizing::Flex(1.0);
+    child.intrinsic_height = BoxSizing::Flex(1.0);
 
     let mut root = BlockLayout::new(child);
-    root.intrinsic_width = BoxSizing::Flex(1);
-    root.intrinsic_height = BoxSizing::Flex(1);
+    root.intrinsic_width = BoxSizing::Flex(1.0);
+    root.intrinsic_height = BoxSizing::Flex(1.0);
 
     root.padding.top = 20;
     root.padding.bottom = 10;
     root.padding.left = 30;
     root.padding.right = 50;
 
-    solve_layout(&mut root, Size::unit(1000.0));
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
     let child = root.child();
     let width = (1000 - root.padding.left - root.padding.right) as f32;
     let height = (1000 - root.padding.top - root.padding.bottom) as f32;
@@ -77,3 +80,56 @@ fn padding_in_min_constraints() {
     assert_eq!(root.constraints().min_width, min_width);
     assert_eq!(root.constraints().min_height, min_height);
 }
+
+fn aligned_block(main: MainAxisAlignment, cross: CrossAxisAlignment) -> BlockLayout {
+    let child = EmptyLayout::new().fixed(100.0, 50.0);
+    let mut root = BlockLayout::new(child)
+        .fixed(300.0, 200.0)
+        .main_axis_alignment(main)
+        .cross_axis_alignment(cross);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+    root
+}
+
+#[test]
+fn align_child_start() {
+    let root = aligned_block(MainAxisAlignment::Start, CrossAxisAlignment::Start);
+    assert_eq!(root.child().position(), Position::new(0.0, 0.0));
+}
+
+#[test]
+fn align_child_center() {
+    let root = aligned_block(MainAxisAlignment::Center, CrossAxisAlignment::Center);
+    assert_eq!(root.child().position(), Position::new(100.0, 75.0));
+}
+
+#[test]
+fn align_child_end() {
+    let root = aligned_block(MainAxisAlignment::End, CrossAxisAlignment::End);
+    assert_eq!(root.child().position(), Position::new(200.0, 150.0));
+}
+
+#[test]
+fn align_child_space_evenly() {
+    let root = aligned_block(MainAxisAlignment::SpaceEvenly, CrossAxisAlignment::Start);
+    assert_eq!(root.child().position(), Position::new(100.0, 0.0));
+}
+
+#[test]
+fn align_child_space_between() {
+    let root = aligned_block(MainAxisAlignment::SpaceBetween, CrossAxisAlignment::Start);
+    assert_eq!(root.child().position(), Position::new(0.0, 0.0));
+}
+
+#[test]
+fn align_child_with_padding() {
+    let child = EmptyLayout::new().fixed(100.0, 50.0);
+    let mut root = BlockLayout::new(child)
+        .fixed(300.0, 200.0)
+        .padding_left(20)
+        .padding_top(10)
+        .main_axis_alignment(MainAxisAlignment::End)
+        .cross_axis_alignment(CrossAxisAlignment::Center);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+    assert_eq!(root.child().position(), Position::new(200.0, 80.0));
+}
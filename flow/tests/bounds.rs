@@ -0,0 +1,97 @@
+use flow::{
+    BlockLayout, EmptyLayout, HorizontalLayout, Layout, Size, VerticalLayout, solve_layout,
+};
+
+#[test]
+fn flex_respects_max_width() {
+    let mut layout = HorizontalLayout::new().fixed_width(1000.0);
+    layout.push(EmptyLayout::new().fill_width().max_width(600.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().width, 600.0);
+}
+
+#[test]
+fn fit_respects_min_height() {
+    let mut layout = VerticalLayout::new().min_height(48.0);
+    layout.push(EmptyLayout::new().fixed(100.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(100.0, 48.0));
+}
+
+#[test]
+fn min_bounds_propagate_to_parent() {
+    let mut layout = HorizontalLayout::new();
+    layout.push(EmptyLayout::new().min_width(30.0).min_height(40.0));
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(80.0, 40.0));
+}
+
+#[test]
+fn fit_respects_max_width() {
+    let mut layout = BlockLayout::new(EmptyLayout::new().fixed(300.0, 20.0)).max_width(200.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size().width, 200.0);
+}
+
+#[test]
+fn root_respects_bounds() {
+    let mut layout = EmptyLayout::new().fill().max_width(400.0).max_height(300.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(400.0, 300.0));
+}
+
+#[test]
+fn aspect_ratio_from_fixed_width() {
+    let mut layout = EmptyLayout::new()
+        .fixed_width(160.0)
+        .aspect_ratio(16.0 / 9.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(160.0, 90.0));
+}
+
+#[test]
+fn aspect_ratio_from_fixed_height() {
+    let mut layout = EmptyLayout::new().fixed_height(100.0).aspect_ratio(2.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(200.0, 100.0));
+}
+
+#[test]
+fn aspect_ratio_from_flex_width() {
+    let mut layout = VerticalLayout::new().fixed_width(320.0);
+    layout.push(EmptyLayout::new().fill_width().aspect_ratio(16.0 / 9.0));
+    layout.push(EmptyLayout::new().fixed(100.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size(), Size::new(320.0, 180.0));
+    assert_eq!(layout.children()[1].position().y, 180.0);
+    assert_eq!(layout.size().height, 200.0);
+}
+
+#[test]
+fn aspect_ratio_from_fit_width() {
+    let mut layout = HorizontalLayout::new().aspect_ratio(1.0);
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::unit(50.0));
+}
+
+#[test]
+fn aspect_ratio_respects_bounds() {
+    let mut layout = EmptyLayout::new()
+        .fixed_width(400.0)
+        .aspect_ratio(1.0)
+        .max_height(100.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(400.0, 100.0));
+}
@@ -0,0 +1,96 @@
+use flow::{
+    EmptyLayout, HorizontalLayout, Layout, Position, Size, VerticalLayout, count_recomputed,
+    solve_layout,
+};
+
+fn row() -> HorizontalLayout {
+    let mut row = HorizontalLayout::new().fixed(100.0, 20.0);
+    row.push(EmptyLayout::new().fixed(50.0, 20.0));
+    row.push(EmptyLayout::new().fixed(50.0, 20.0));
+    row
+}
+
+#[test]
+fn first_solve_computes_every_node() {
+    let mut layout = VerticalLayout::new();
+    layout.push(row());
+    layout.push(row());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(count_recomputed(&layout), 7);
+}
+
+#[test]
+fn skip_unchanged_tree() {
+    let mut layout = VerticalLayout::new().fill();
+    layout.push(row());
+    layout.push(EmptyLayout::new().fill());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(count_recomputed(&layout), 0);
+    assert_eq!(layout.size(), Size::unit(1000.0));
+    assert_eq!(layout.children()[1].size(), Size::new(1000.0, 980.0));
+}
+
+#[test]
+fn resize_skips_fixed_subtrees() {
+    let mut layout = VerticalLayout::new().fill();
+    layout.push(row());
+    layout.push(EmptyLayout::new().fill());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    solve_layout(&mut layout, Size::unit(500.0)).unwrap();
+
+    assert_eq!(count_recomputed(&layout), 2);
+    assert_eq!(layout.children()[1].size(), Size::new(500.0, 480.0));
+}
+
+#[test]
+fn push_marks_dirty() {
+    let mut layout = HorizontalLayout::new();
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    assert!(!layout.is_dirty());
+
+    layout.push(EmptyLayout::new().fixed(50.0, 40.0));
+    assert!(layout.is_dirty());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(count_recomputed(&layout), 2);
+    assert_eq!(layout.size(), Size::new(150.0, 40.0));
+    assert_eq!(layout.children()[2].position(), Position::new(100.0, 0.0));
+}
+
+#[test]
+fn mark_dirty_after_changing_fields() {
+    let mut layout = HorizontalLayout::new();
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    layout.spacing = 10;
+    layout.mark_dirty();
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(count_recomputed(&layout), 1);
+    assert_eq!(layout.size().width, 110.0);
+    assert_eq!(layout.children()[1].position(), Position::new(60.0, 0.0));
+}
+
+#[test]
+fn moved_subtree_is_repositioned() {
+    let mut layout = VerticalLayout::new();
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    layout.push(row());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    layout.spacing = 30;
+    layout.mark_dirty();
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let row = &layout.children()[1];
+    assert_eq!(count_recomputed(&layout), 1);
+    assert_eq!(row.position(), Position::new(0.0, 50.0));
+    assert_eq!(row.children()[1].position(), Position::new(50.0, 50.0));
+}
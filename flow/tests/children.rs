@@ -0,0 +1,158 @@
+use flow::{
+    AbsoluteLayout, Alignment, BlockLayout, EmptyLayout, GridLayout, HorizontalLayout, Layout,
+    Position, Size, StackLayout, VerticalLayout, count_recomputed, solve_layout,
+};
+
+fn row() -> HorizontalLayout {
+    let mut row = HorizontalLayout::new().fixed(100.0, 20.0);
+    row.push(EmptyLayout::new().fixed(50.0, 20.0));
+    row.push(EmptyLayout::new().fixed(50.0, 20.0));
+    row
+}
+
+#[test]
+fn find_nested_layout() {
+    let row = row();
+    let id = row.children()[1].id();
+    let mut layout = VerticalLayout::new();
+    layout.push(EmptyLayout::new());
+    layout.push(BlockLayout::new(row));
+
+    assert_eq!(layout.find(id).unwrap().id(), id);
+    assert!(layout.find(layout.id()).is_none());
+    assert!(EmptyLayout::new().find(id).is_none());
+}
+
+#[test]
+fn find_mut_marks_path_dirty() {
+    let row = row();
+    let id = row.children()[0].id();
+    let mut layout = VerticalLayout::new();
+    layout.push(row);
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    layout.find_mut(id).unwrap().set_size(Size::unit(0.0));
+    assert!(layout.is_dirty());
+    assert!(layout.children()[0].is_dirty());
+    assert!(!layout.children()[1].is_dirty());
+}
+
+#[test]
+fn update_nested_layout() {
+    let first = row();
+    let id = first.children()[0].id();
+    let mut layout = VerticalLayout::new();
+    layout.push(first);
+    layout.push(row());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let child = EmptyLayout::new().fixed(80.0, 40.0);
+    let old = layout.replace(id, Box::new(child)).unwrap();
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(old.id(), id);
+    assert!(layout.find(id).is_none());
+    assert_eq!(count_recomputed(&layout), 3);
+    assert_eq!(
+        layout.children()[0].children()[0].size(),
+        Size::new(80.0, 40.0)
+    );
+    assert_eq!(layout.children()[1].position(), Position::new(0.0, 20.0));
+}
+
+#[test]
+fn insert_child() {
+    let mut layout = HorizontalLayout::new();
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    layout.insert(1, EmptyLayout::new().fixed(30.0, 20.0));
+    assert!(layout.is_dirty());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size().width, 130.0);
+    assert_eq!(layout.children()[1].size().width, 30.0);
+    assert_eq!(layout.children()[2].position().x, 80.0);
+}
+
+#[test]
+fn remove_nested_child() {
+    let row = row();
+    let id = row.children()[0].id();
+    let mut layout = VerticalLayout::new();
+    layout.push(row);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let removed = layout.remove(id).unwrap();
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(removed.id(), id);
+    assert!(layout.remove(id).is_none());
+    assert_eq!(layout.children()[0].children().len(), 1);
+    assert_eq!(layout.children()[0].children()[0].position().x, 0.0);
+}
+
+#[test]
+fn remove_keeps_placements() {
+    let mut layout = StackLayout::new().fixed(100.0, 100.0);
+    let child = EmptyLayout::new().fixed(10.0, 10.0);
+    let id = child.id();
+    layout.place(child, Alignment::Center, Position::default());
+    layout.place(
+        EmptyLayout::new().fixed(10.0, 10.0),
+        Alignment::BottomRight,
+        Position::default(),
+    );
+    layout.remove(id).unwrap();
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].position(), Position::new(90.0, 90.0));
+}
+
+#[test]
+fn insert_into_grid() {
+    let mut layout = GridLayout::new();
+    layout.push(EmptyLayout::new().fixed(10.0, 10.0));
+    layout.push(EmptyLayout::new().fixed(10.0, 10.0));
+    layout.insert(0, EmptyLayout::new().fixed(20.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children().len(), 3);
+    assert_eq!(layout.children()[0].size(), Size::unit(20.0));
+}
+
+#[test]
+fn children_of_absolute_layout() {
+    let row = row();
+    let id = row.children()[1].id();
+    let mut layout = StackLayout::new();
+    layout.push(AbsoluteLayout::new(row));
+
+    assert!(layout.find(id).is_some());
+    assert_eq!(layout.remove(id).unwrap().id(), id);
+    assert_eq!(layout.children()[0].children().len(), 1);
+}
+
+#[test]
+fn visit_depth_first() {
+    let mut layout = VerticalLayout::new();
+    layout.push(row());
+    layout.push(EmptyLayout::new());
+
+    let mut ids = vec![];
+    flow::visit(&layout, &mut |layout, depth| ids.push((layout.id(), depth)));
+
+    let row = &layout.children()[0];
+    assert_eq!(
+        ids,
+        [
+            (layout.id(), 0),
+            (row.id(), 1),
+            (row.children()[0].id(), 2),
+            (row.children()[1].id(), 2),
+            (layout.children()[1].id(), 1),
+        ]
+    );
+}
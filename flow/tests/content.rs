@@ -0,0 +1,136 @@
+use flow::{
+    Axis, BoxConstraints, BoxSizing, EmptyLayout, GridLayout, HorizontalLayout, Layout, Margin,
+    MeasuredLayout, ScrollLayout, Size, VerticalLayout, WrapLayout, solve_layout,
+};
+
+/// Text with five words that are 40 pixels wide and 20 pixels tall,
+/// wrapped to the max width.
+fn text() -> MeasuredLayout {
+    MeasuredLayout::new(|constraints: BoxConstraints| {
+        let per_line = (constraints.max_width / 40.0).floor().clamp(1.0, 5.0);
+        let lines = (5.0 / per_line).ceil();
+        Size::new(per_line * 40.0, lines * 20.0)
+    })
+}
+
+#[test]
+fn measured_content_size() {
+    let text = text();
+
+    assert_eq!(text.min_content_width(None), 40.0);
+    assert_eq!(text.max_content_width(None), 200.0);
+    assert_eq!(text.min_content_height(Some(80.0)), 60.0);
+    assert_eq!(text.max_content_height(None), 20.0);
+}
+
+#[test]
+fn fixed_size_is_content_size() {
+    let layout = EmptyLayout::new().fixed(50.0, 20.0);
+
+    assert_eq!(layout.min_content_width(None), 50.0);
+    assert_eq!(layout.max_content_height(None), 20.0);
+    assert_eq!(
+        EmptyLayout::new().min_width(30.0).max_content_width(None),
+        30.0
+    );
+}
+
+#[test]
+fn horizontal_content_size() {
+    let mut layout = HorizontalLayout::new().padding_left(10);
+    layout.spacing = 10;
+    layout.push(text());
+    layout.push(EmptyLayout::new().fixed(50.0, 30.0).margin_right(5));
+
+    assert_eq!(layout.min_content_width(None), 115.0);
+    assert_eq!(layout.max_content_width(None), 275.0);
+    assert_eq!(layout.max_content_height(None), 30.0);
+}
+
+#[test]
+fn vertical_content_size() {
+    let mut layout = VerticalLayout::new().padding_top(10);
+    layout.spacing = 10;
+    layout.push(text());
+    layout.push(EmptyLayout::new().fixed(50.0, 30.0));
+
+    assert_eq!(layout.min_content_width(None), 50.0);
+    assert_eq!(layout.max_content_width(None), 200.0);
+    assert_eq!(layout.min_content_height(Some(80.0)), 110.0);
+    assert_eq!(layout.min_content_height(None), 70.0);
+}
+
+#[test]
+fn wrap_content_size() {
+    let mut layout = WrapLayout::new();
+    layout.spacing = 10;
+    for _ in 0..3 {
+        layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    }
+
+    assert_eq!(layout.min_content_width(None), 50.0);
+    assert_eq!(layout.max_content_width(None), 170.0);
+    assert_eq!(layout.min_content_height(Some(120.0)), 40.0);
+}
+
+#[test]
+fn grid_content_size() {
+    let mut layout = GridLayout::new().columns([BoxSizing::Fit, BoxSizing::Flex(1.0)]);
+    layout.column_gap = 10;
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    layout.push(text());
+    layout.push(EmptyLayout::new().fixed(80.0, 20.0));
+
+    assert_eq!(layout.min_content_width(None), 130.0);
+    assert_eq!(layout.max_content_width(None), 290.0);
+    assert_eq!(layout.max_content_height(None), 40.0);
+}
+
+#[test]
+fn scroll_shrinks_along_axis() {
+    let layout = ScrollLayout::new(EmptyLayout::new().fixed(100.0, 500.0));
+
+    assert_eq!(layout.min_content_height(None), 0.0);
+    assert_eq!(layout.max_content_height(None), 500.0);
+    assert_eq!(layout.min_content_width(None), 100.0);
+
+    let layout = layout.axis(Axis::Horizontal);
+    assert_eq!(layout.min_content_width(None), 0.0);
+}
+
+#[test]
+fn fit_child_is_content_width() {
+    let mut column = VerticalLayout::new();
+    column.push(EmptyLayout::new().fixed(100.0, 20.0));
+    column.push(EmptyLayout::new().fill_width().fixed_height(20.0));
+
+    let mut layout = HorizontalLayout::new();
+    layout.push(column);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let column = &layout.children()[0];
+    assert_eq!(column.size(), Size::new(100.0, 40.0));
+    assert_eq!(column.children()[1].size().width, 100.0);
+}
+
+#[test]
+fn fit_text_wraps_to_remaining_space() {
+    let mut layout = HorizontalLayout::new().fixed_width(200.0);
+    layout.push(EmptyLayout::new().fixed(80.0, 20.0));
+    layout.push(text());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[1].size(), Size::new(120.0, 40.0));
+}
+
+#[test]
+fn flex_after_fit_content() {
+    let mut layout = HorizontalLayout::new().fixed_width(400.0);
+    layout.push(text().margin(Margin::sides(10, 0)));
+    layout.push(EmptyLayout::new().fill_width());
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert!(warnings.is_empty());
+    assert_eq!(layout.children()[0].size().width, 200.0);
+    assert_eq!(layout.children()[1].size().width, 180.0);
+}
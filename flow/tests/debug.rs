@@ -0,0 +1,95 @@
+use flow::debug::{dump_tree, dump_tree_json, dump_tree_json_stable};
+use flow::{
+    AbsoluteLayout, EmptyLayout, HorizontalLayout, Layout, Size, VerticalLayout, solve_layout,
+};
+
+fn tree() -> HorizontalLayout {
+    let mut column = VerticalLayout::new();
+    column.push(EmptyLayout::new().fixed(50.0, 20.0));
+
+    let mut layout = HorizontalLayout::new().fixed_height(40.0);
+    layout.push(column);
+    layout.push(EmptyLayout::new().fill_width());
+    solve_layout(&mut layout, Size::new(200.0, 100.0)).unwrap();
+    layout
+}
+
+#[test]
+fn dump_text() {
+    let layout = tree();
+    let column = &layout.children()[0];
+    let ids = [
+        layout.id(),
+        column.id(),
+        column.children()[0].id(),
+        layout.children()[1].id(),
+    ];
+
+    let expected = format!(
+        "HorizontalLayout {:?} width: Fit height: Fixed(40.0) min: 50x40 max: 200x40 size: 50x40 at (0, 0)
+  VerticalLayout {:?} width: Fit height: Fit min: 50x20 max: 50x20 size: 50x20 at (0, 0)
+    EmptyLayout {:?} width: Fixed(50.0) height: Fixed(20.0) min: 50x20 max: 50x20 size: 50x20 at (0, 0)
+  EmptyLayout {:?} width: Flex(1.0) height: Fit min: 0x0 max: 150x0 size: 150x0 at (50, 0)
+",
+        ids[0], ids[1], ids[2], ids[3]
+    );
+    assert_eq!(dump_tree(&layout), expected);
+}
+
+#[test]
+fn dump_json() {
+    let child = EmptyLayout::new().fixed(50.0, 20.0);
+    let child_id = child.id();
+    let mut layout = VerticalLayout::new();
+    layout.push(child);
+    solve_layout(&mut layout, Size::unit(100.0)).unwrap();
+
+    let expected = format!(
+        r#"{{
+  "type": "VerticalLayout",
+  "id": "{:?}",
+  "path": "0",
+  "intrinsic_width": "Fit",
+  "intrinsic_height": "Fit",
+  "constraints": {{"min_width": 50, "max_width": 100, "min_height": 20, "max_height": 100}},
+  "size": {{"width": 50, "height": 20}},
+  "position": {{"x": 0, "y": 0}},
+  "children": [
+    {{
+      "type": "EmptyLayout",
+      "id": "{:?}",
+      "path": "0/0",
+      "intrinsic_width": "Fixed(50.0)",
+      "intrinsic_height": "Fixed(20.0)",
+      "constraints": {{"min_width": 50, "max_width": 50, "min_height": 20, "max_height": 20}},
+      "size": {{"width": 50, "height": 20}},
+      "position": {{"x": 0, "y": 0}},
+      "children": []
+    }}
+  ]
+}}
+"#,
+        layout.id(),
+        child_id
+    );
+    assert_eq!(dump_tree_json(&layout), expected);
+}
+
+#[test]
+fn json_is_stable() {
+    let build = || {
+        let mut layout = tree();
+        layout.push(AbsoluteLayout::new(EmptyLayout::new().fixed(10.0, 10.0)).right(0.0));
+        solve_layout(&mut layout, Size::new(200.0, 100.0)).unwrap();
+        layout
+    };
+    let json = dump_tree_json_stable(&build());
+
+    assert_eq!(json, dump_tree_json_stable(&build()));
+    assert!(!json.contains("\"id\""));
+    assert_eq!(json.matches("\"type\"").count(), 6);
+    assert!(json.contains("\"type\": \"AbsoluteLayout\",\n      \"path\": \"0/2\""));
+    assert!(json.contains("\"path\": \"0/2/0\""));
+    assert_eq!(json.matches('{').count(), json.matches('}').count());
+    assert_eq!(json.matches('[').count(), json.matches(']').count());
+}
@@ -0,0 +1,126 @@
+use flow::{
+    AbsoluteLayout, Alignment, BlockLayout, BoxSizing, EmptyLayout, GridLayout, HorizontalLayout,
+    Layout, LayoutDirection, MainAxisAlignment, Margin, Position, Size, StackLayout,
+    VerticalLayout, WrapLayout, solve_layout,
+};
+
+/// Solve the layout in both directions and check that every layout in
+/// the right to left tree is the mirror of the left to right tree.
+fn assert_mirrored(mut layout: impl Layout) {
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    let mut ltr = vec![];
+    flow::visit(&layout, &mut |l, _| ltr.push((l.position(), l.size())));
+
+    layout.set_direction(LayoutDirection::RightToLeft);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    let mut rtl = vec![];
+    flow::visit(&layout, &mut |l, _| rtl.push((l.position(), l.size())));
+
+    let width = layout.size().width;
+    assert_eq!(ltr.len(), rtl.len());
+    for ((position, size), (mirrored, mirrored_size)) in ltr.into_iter().zip(rtl) {
+        assert_eq!(size, mirrored_size);
+        assert_eq!(mirrored.y, position.y);
+        assert_eq!(mirrored.x, width - position.x - size.width);
+    }
+}
+
+#[test]
+fn horizontal_is_mirrored() {
+    let mut layout = HorizontalLayout::new()
+        .fixed_width(500.0)
+        .padding_left(20)
+        .padding_right(5);
+    layout.spacing = 10;
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0).margin_left(15));
+    layout.push(EmptyLayout::new().fill_width().fixed_height(20.0));
+    layout.push(EmptyLayout::new().fixed(80.0, 20.0));
+
+    let mut rtl = HorizontalLayout::new();
+    rtl.set_direction(LayoutDirection::RightToLeft);
+    rtl.push(EmptyLayout::new().fixed(50.0, 20.0));
+    rtl.push(EmptyLayout::new().fixed(80.0, 20.0));
+    solve_layout(&mut rtl, Size::unit(1000.0)).unwrap();
+    assert_eq!(rtl.children()[0].position(), Position::new(80.0, 0.0));
+    assert_eq!(rtl.children()[1].position(), Position::new(0.0, 0.0));
+
+    assert_mirrored(layout);
+}
+
+#[test]
+fn main_axis_alignment_is_mirrored() {
+    for alignment in [
+        MainAxisAlignment::Start,
+        MainAxisAlignment::Center,
+        MainAxisAlignment::End,
+        MainAxisAlignment::SpaceBetween,
+    ] {
+        let mut layout = HorizontalLayout::new()
+            .fixed_width(400.0)
+            .padding_left(30)
+            .main_axis_alignment(alignment);
+        layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+        layout.push(EmptyLayout::new().fixed(70.0, 20.0));
+        assert_mirrored(layout);
+    }
+}
+
+#[test]
+fn direction_is_passed_to_subtree() {
+    let mut row = HorizontalLayout::new().padding_left(10);
+    row.push(EmptyLayout::new().fixed(40.0, 40.0));
+    row.push(
+        EmptyLayout::new()
+            .fixed(60.0, 40.0)
+            .margin(Margin::new(5, 0, 0, 0)),
+    );
+
+    let mut layout = VerticalLayout::new().fixed_width(300.0).padding_left(25);
+    layout.push(row);
+    layout.push(EmptyLayout::new().fill_width().fixed_height(30.0));
+    layout.push(BlockLayout::new(EmptyLayout::new().fixed(20.0, 20.0)).padding_left(8));
+    layout.set_direction(LayoutDirection::RightToLeft);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    flow::visit(&layout, &mut |l, _| {
+        assert_eq!(l.direction(), LayoutDirection::RightToLeft)
+    });
+    layout.set_direction(LayoutDirection::LeftToRight);
+    assert_mirrored(layout);
+}
+
+#[test]
+fn wrap_and_grid_are_mirrored() {
+    let mut wrap = WrapLayout::new().fixed_width(200.0).padding_left(10);
+    wrap.spacing = 10;
+    for width in [60.0, 80.0, 50.0, 90.0] {
+        wrap.push(EmptyLayout::new().fixed(width, 20.0));
+    }
+    assert_mirrored(wrap);
+
+    let mut grid = GridLayout::new()
+        .columns([BoxSizing::Fixed(50.0), BoxSizing::Flex(1.0), BoxSizing::Fit])
+        .fixed_width(300.0);
+    grid.column_gap = 10;
+    for _ in 0..6 {
+        grid.push(EmptyLayout::new().fixed(30.0, 20.0).margin_left(5));
+    }
+    assert_mirrored(grid);
+}
+
+#[test]
+fn stack_and_absolute_are_mirrored() {
+    let mut layout = StackLayout::new().fixed(200.0, 100.0).padding_left(10);
+    layout.place(
+        EmptyLayout::new().fixed(20.0, 20.0),
+        Alignment::TopLeft,
+        Position::new(15.0, 5.0),
+    );
+    layout.place(
+        EmptyLayout::new().fixed(30.0, 20.0),
+        Alignment::BottomRight,
+        Position::default(),
+    );
+    layout.push(AbsoluteLayout::new(EmptyLayout::new().fixed(40.0, 10.0)).left(12.0));
+    assert_mirrored(layout);
+}
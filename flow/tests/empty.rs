@@ -3,9 +3,9 @@ use flow::{BoxSizing, EmptyLayout, Layout, Size};
 #[test]
 fn fill_window() {
     let mut layout = EmptyLayout::new();
-    layout.intrinsic_width = BoxSizing::Flex(1);
-    layout.intrinsic_height = BoxSizing::Flex(1);
-    flow::solve_layout(&mut layout, Size::new(50.0, 700.0));
+    layout.intrinsic_width = BoxSizing::Flex(1.0);
+    layout.intrinsic_height = BoxSizing::Flex(1.0);
+    flow::solve_layout(&mut layout, Size::new(50.0, 700.0)).unwrap();
     assert_eq!(layout.size(), Size::new(50.0, 700.0));
 }
 
@@ -14,7 +14,7 @@ fn fit() {
     let mut layout = EmptyLayout::new();
     layout.intrinsic_width = BoxSizing::Fit;
     layout.intrinsic_height = BoxSizing::Fit;
-    flow::solve_layout(&mut layout, Size::new(50.0, 700.0));
+    flow::solve_layout(&mut layout, Size::new(50.0, 700.0)).unwrap();
     assert_eq!(layout.size(), Size::unit(0.0));
 }
 
@@ -23,6 +23,14 @@ fn fixed_size() {
     let mut layout = EmptyLayout::new();
     layout.intrinsic_width = BoxSizing::Fixed(250.0);
     layout.intrinsic_height = BoxSizing::Fixed(20.0);
-    flow::solve_layout(&mut layout, Size::unit(1000.0));
+    flow::solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
     assert_eq!(layout.size(), Size::new(250.0, 20.0));
 }
+
+#[test]
+fn percent_of_window() {
+    let mut layout = EmptyLayout::new().width_percent(50.0).height_percent(25.0);
+    flow::solve_layout(&mut layout, Size::new(800.0, 400.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(400.0, 100.0));
+}
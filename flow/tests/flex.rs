@@ -0,0 +1,204 @@
+use flow::{
+    Axis, BoxSizing, EmptyLayout, GridLayout, HorizontalLayout, Layout, LayoutError, LayoutTree,
+    Node, Size, VerticalLayout, solve_layout,
+};
+
+#[test]
+fn fractional_weights() {
+    let mut layout = HorizontalLayout::new().fixed(500.0, 20.0);
+    layout.push(EmptyLayout::new().flex_width(1.5));
+    layout.push(EmptyLayout::new().flex_width(1.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().width, 300.0);
+    assert_eq!(layout.children()[1].size().width, 200.0);
+}
+
+#[test]
+fn weights_past_u8() {
+    let mut layout = VerticalLayout::new().fixed(20.0, 1000.0);
+    layout.push(EmptyLayout::new().flex_height(300.0));
+    layout.push(EmptyLayout::new().flex_height(700.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().height, 300.0);
+    assert_eq!(layout.children()[1].size().height, 700.0);
+}
+
+#[test]
+fn flex_both_axes() {
+    let layout = EmptyLayout::new().flex(2.5);
+    assert_eq!(layout.intrinsic_width, BoxSizing::Flex(2.5));
+    assert_eq!(layout.intrinsic_height, BoxSizing::Flex(2.5));
+}
+
+#[test]
+fn fractional_grid_tracks() {
+    let mut layout = GridLayout::new()
+        .columns([BoxSizing::Flex(0.5), BoxSizing::Flex(1.5)])
+        .fixed_width(400.0);
+    layout.push(EmptyLayout::new());
+    layout.push(EmptyLayout::new());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.column_sizes(), [100.0, 300.0]);
+}
+
+#[test]
+fn zero_weight_is_an_error() {
+    let child = EmptyLayout::new().flex_width(0.0);
+    let id = child.id();
+    let mut layout = HorizontalLayout::new().fixed(100.0, 100.0);
+    layout.push(child);
+
+    let error = solve_layout(&mut layout, Size::unit(1000.0)).unwrap_err();
+    assert_eq!(
+        error,
+        LayoutError::InvalidFlex {
+            id,
+            axis: Axis::Horizontal,
+            flex: 0.0
+        }
+    );
+}
+
+#[test]
+fn nan_weight_is_an_error() {
+    let mut layout = VerticalLayout::new().fixed(100.0, 100.0);
+    layout.push(EmptyLayout::new().flex_height(f32::NAN));
+
+    let error = solve_layout(&mut layout, Size::unit(1000.0)).unwrap_err();
+    assert!(matches!(
+        error,
+        LayoutError::InvalidFlex {
+            axis: Axis::Vertical,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn infinite_weight_next_to_sibling() {
+    let child = EmptyLayout::new().flex_width(f32::INFINITY);
+    let id = child.id();
+    let mut layout = HorizontalLayout::new().fixed(500.0, 20.0);
+    layout.push(child);
+    layout.push(EmptyLayout::new().flex_width(1.0));
+
+    let error = solve_layout(&mut layout, Size::unit(1000.0)).unwrap_err();
+    assert_eq!(
+        error,
+        LayoutError::InvalidFlex {
+            id,
+            axis: Axis::Horizontal,
+            flex: f32::INFINITY
+        }
+    );
+
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::horizontal().fixed(500.0, 20.0));
+    tree.set_root(root).unwrap();
+    tree.insert_child(root, Node::empty().flex_width(f32::INFINITY))
+        .unwrap();
+    tree.insert_child(root, Node::empty().flex_width(1.0))
+        .unwrap();
+    assert!(matches!(
+        tree.solve(Size::unit(1000.0)),
+        Err(LayoutError::InvalidFlex { .. })
+    ));
+}
+
+#[test]
+fn tree_weights() {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::horizontal().fixed(400.0, 10.0));
+    let a = tree
+        .insert_child(root, Node::empty().flex_width(3.0))
+        .unwrap();
+    let b = tree
+        .insert_child(root, Node::empty().flex_width(1.0))
+        .unwrap();
+    tree.set_root(root).unwrap();
+    tree.solve(Size::unit(1000.0)).unwrap();
+
+    assert_eq!(tree.get(a).unwrap().size().width, 300.0);
+    assert_eq!(tree.get(b).unwrap().size().width, 100.0);
+
+    let c = tree
+        .insert_child(root, Node::empty().flex_width(-1.0))
+        .unwrap();
+    let id = tree.get(c).unwrap().id();
+    assert!(matches!(
+        tree.solve(Size::unit(1000.0)),
+        Err(LayoutError::InvalidFlex { id: error, .. }) if error == id
+    ));
+}
+
+#[test]
+fn invalid_grid_tracks() {
+    for child in [EmptyLayout::new().fill(), EmptyLayout::new()] {
+        let mut layout = GridLayout::new()
+            .columns([BoxSizing::Flex(0.0), BoxSizing::Fixed(50.0)])
+            .fixed_width(200.0);
+        layout.push(child);
+        let id = layout.id();
+
+        let error = solve_layout(&mut layout, Size::unit(1000.0)).unwrap_err();
+        assert_eq!(
+            error,
+            LayoutError::InvalidFlex {
+                id,
+                axis: Axis::Horizontal,
+                flex: 0.0
+            }
+        );
+    }
+
+    let mut layout = GridLayout::new().rows([BoxSizing::Flex(f32::NAN)]);
+    layout.push(EmptyLayout::new());
+    assert!(matches!(
+        solve_layout(&mut layout, Size::unit(1000.0)),
+        Err(LayoutError::InvalidFlex {
+            axis: Axis::Vertical,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn max_clamped_space_is_shared() {
+    let mut layout = HorizontalLayout::new().fixed(1000.0, 20.0);
+    layout.push(EmptyLayout::new().fill().max_width(300.0));
+    layout.push(EmptyLayout::new().fill());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().width, 300.0);
+    assert_eq!(layout.children()[1].size().width, 700.0);
+    assert_eq!(layout.children()[1].position().x, 300.0);
+}
+
+#[test]
+fn min_clamped_space_is_taken_from_siblings() {
+    let mut layout = HorizontalLayout::new().fixed(1000.0, 20.0);
+    layout.push(EmptyLayout::new().fill().min_width(800.0));
+    layout.push(EmptyLayout::new().fill());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().width, 800.0);
+    assert_eq!(layout.children()[1].size().width, 200.0);
+}
+
+#[test]
+fn vertical_clamped_space_is_shared() {
+    let mut layout = VerticalLayout::new().fixed(20.0, 1000.0);
+    layout.push(EmptyLayout::new().fill().max_height(300.0));
+    layout.push(EmptyLayout::new().fill());
+    layout.push(EmptyLayout::new().fill().min_height(500.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    // The min clamp takes more space than the max clamp gives back, so
+    // only the min is frozen and the others share the rest evenly
+    assert_eq!(layout.children()[0].size().height, 250.0);
+    assert_eq!(layout.children()[1].size().height, 250.0);
+    assert_eq!(layout.children()[2].size().height, 500.0);
+}
@@ -0,0 +1,169 @@
+use flow::{
+    BoxSizing, EmptyLayout, GridLayout, GridPlacement, Layout, Position, Size, solve_layout,
+};
+
+fn positions(grid: &GridLayout) -> Vec<Position<f32>> {
+    grid.children().iter().map(|c| c.position()).collect()
+}
+
+#[test]
+fn fixed_tracks() {
+    let mut grid = GridLayout::new()
+        .columns([BoxSizing::Fixed(100.0), BoxSizing::Fixed(150.0)])
+        .rows([BoxSizing::Fixed(40.0), BoxSizing::Fixed(60.0)]);
+    grid.push(EmptyLayout::new());
+    solve_layout(&mut grid, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(grid.size(), Size::new(250.0, 100.0));
+}
+
+#[test]
+fn fit_tracks() {
+    let mut grid = GridLayout::new().columns([BoxSizing::Fit, BoxSizing::Fit]);
+    grid.push(EmptyLayout::new().fixed(100.0, 20.0));
+    grid.push(EmptyLayout::new().fixed(40.0, 30.0));
+    grid.push(EmptyLayout::new().fixed(60.0, 50.0));
+    grid.push(EmptyLayout::new().fixed(80.0, 10.0));
+    solve_layout(&mut grid, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(grid.column_sizes(), [100.0, 80.0]);
+    assert_eq!(grid.row_sizes(), [30.0, 50.0]);
+    assert_eq!(grid.size(), Size::new(180.0, 80.0));
+}
+
+#[test]
+fn flex_tracks() {
+    let mut grid = GridLayout::new()
+        .fill()
+        .columns([
+            BoxSizing::Fixed(100.0),
+            BoxSizing::Flex(1.0),
+            BoxSizing::Flex(2.0),
+        ])
+        .rows([BoxSizing::Flex(1.0), BoxSizing::Fixed(50.0)]);
+    grid.column_gap = 10;
+    grid.row_gap = 20;
+    grid.push(EmptyLayout::new().fill());
+    solve_layout(&mut grid, Size::new(420.0, 300.0)).unwrap();
+
+    assert_eq!(grid.column_sizes(), [100.0, 100.0, 200.0]);
+    assert_eq!(grid.row_sizes(), [230.0, 50.0]);
+    assert_eq!(grid.children()[0].size(), Size::new(100.0, 230.0));
+}
+
+#[test]
+fn gaps_and_padding() {
+    let mut grid = GridLayout::new()
+        .columns([BoxSizing::Fixed(100.0), BoxSizing::Fixed(100.0)])
+        .padding_left(10)
+        .padding_top(5);
+    grid.column_gap = 12;
+    grid.row_gap = 8;
+    for _ in 0..4 {
+        grid.push(EmptyLayout::new().fixed(50.0, 50.0));
+    }
+    solve_layout(&mut grid, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(
+        positions(&grid),
+        [
+            Position::new(10.0, 5.0),
+            Position::new(122.0, 5.0),
+            Position::new(10.0, 63.0),
+            Position::new(122.0, 63.0),
+        ]
+    );
+    assert_eq!(grid.size(), Size::new(222.0, 113.0));
+}
+
+#[test]
+fn explicit_placement() {
+    let mut grid = GridLayout::new()
+        .columns([BoxSizing::Fixed(100.0); 3])
+        .rows([BoxSizing::Fixed(40.0); 2]);
+    grid.place(EmptyLayout::new(), GridPlacement::new(1, 2));
+    grid.place(EmptyLayout::new(), GridPlacement::new(0, 1));
+    solve_layout(&mut grid, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(
+        positions(&grid),
+        [Position::new(200.0, 40.0), Position::new(100.0, 0.0)]
+    );
+}
+
+#[test]
+fn spanning_child_fills_area() {
+    let mut grid = GridLayout::new()
+        .columns([BoxSizing::Fixed(100.0); 3])
+        .rows([BoxSizing::Fixed(40.0); 2]);
+    grid.column_gap = 10;
+    grid.row_gap = 10;
+    grid.place(
+        EmptyLayout::new().fill(),
+        GridPlacement::new(0, 1).span(2, 2),
+    );
+    grid.push(EmptyLayout::new());
+    grid.push(EmptyLayout::new());
+    solve_layout(&mut grid, Size::unit(1000.0)).unwrap();
+
+    let children = grid.children();
+    assert_eq!(children[0].size(), Size::new(210.0, 90.0));
+    assert_eq!(children[0].position(), Position::new(110.0, 0.0));
+    assert_eq!(children[1].position(), Position::new(0.0, 0.0));
+    assert_eq!(children[2].position(), Position::new(0.0, 50.0));
+}
+
+#[test]
+fn implicit_rows() {
+    let mut grid = GridLayout::new().columns([BoxSizing::Fixed(50.0)]);
+    grid.push(EmptyLayout::new().fixed(50.0, 20.0));
+    grid.push(EmptyLayout::new().fixed(50.0, 30.0));
+    grid.push(EmptyLayout::new().fixed(50.0, 40.0));
+    solve_layout(&mut grid, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(grid.row_sizes(), [20.0, 30.0, 40.0]);
+    assert_eq!(grid.size().height, 90.0);
+}
+
+#[test]
+fn nested_in_horizontal_layout() {
+    let mut grid = GridLayout::new().columns([BoxSizing::Fixed(50.0), BoxSizing::Fixed(50.0)]);
+    grid.push(EmptyLayout::new().fixed(50.0, 25.0));
+
+    let mut root = flow::HorizontalLayout::new();
+    root.push(EmptyLayout::new().fixed(30.0, 10.0));
+    root.push(grid);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(root.size(), Size::new(130.0, 25.0));
+    assert_eq!(root.children()[1].position(), Position::new(30.0, 0.0));
+}
+
+#[test]
+fn percent_tracks() {
+    let mut grid = GridLayout::new()
+        .fixed_width(400.0)
+        .columns([BoxSizing::Percent(25.0), BoxSizing::Flex(1.0)]);
+    grid.push(EmptyLayout::new().fill_width().fixed_height(10.0));
+    grid.push(EmptyLayout::new().width_percent(50.0).fixed_height(10.0));
+    solve_layout(&mut grid, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(grid.column_sizes(), [100.0, 300.0]);
+    assert_eq!(grid.children()[0].size().width, 100.0);
+    assert_eq!(grid.children()[1].size().width, 150.0);
+}
+
+#[test]
+fn zero_span_covers_one_cell() {
+    let mut layout = GridLayout::new().columns([BoxSizing::Fixed(50.0), BoxSizing::Fixed(80.0)]);
+    let mut placement = GridPlacement::new(0, 1);
+    placement.row_span = 0;
+    placement.column_span = 0;
+    layout.place(EmptyLayout::new().fill(), placement);
+    layout.push(EmptyLayout::new().fixed(20.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size(), Size::new(80.0, 20.0));
+    assert_eq!(positions(&layout)[0], Position::new(50.0, 0.0));
+    assert_eq!(positions(&layout)[1], Position::new(0.0, 0.0));
+}
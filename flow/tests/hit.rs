@@ -0,0 +1,130 @@
+use flow::{
+    AbsoluteLayout, Alignment, EmptyLayout, HorizontalLayout, Layout, Position, ScrollLayout, Size,
+    StackLayout, VerticalLayout, hit_test, hit_test_interactive, solve_layout,
+};
+
+#[test]
+fn path_to_deepest_layout() {
+    let mut row = HorizontalLayout::new().padding_left(10);
+    row.push(EmptyLayout::new().fixed(50.0, 50.0));
+    row.push(EmptyLayout::new().fixed(50.0, 50.0));
+    let mut layout = VerticalLayout::new();
+    layout.push(EmptyLayout::new().fixed(100.0, 20.0));
+    layout.push(row);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let row = &layout.children()[1];
+    let path = hit_test(&layout, Position::new(70.0, 40.0));
+    assert_eq!(path, [layout.id(), row.id(), row.children()[1].id()]);
+
+    // Inside the padding of the row
+    let path = hit_test(&layout, Position::new(5.0, 40.0));
+    assert_eq!(path, [layout.id(), row.id()]);
+}
+
+#[test]
+fn miss_root() {
+    let mut layout = HorizontalLayout::new();
+    layout.push(EmptyLayout::new().fixed(50.0, 50.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert!(hit_test(&layout, Position::new(60.0, 10.0)).is_empty());
+    assert!(hit_test(&layout, Position::new(-1.0, 10.0)).is_empty());
+}
+
+#[test]
+fn topmost_child_is_hit() {
+    let mut layout = StackLayout::new().fixed(100.0, 100.0);
+    layout.push(EmptyLayout::new().fill());
+    layout.place(
+        EmptyLayout::new().fixed(20.0, 20.0),
+        Alignment::Center,
+        Position::default(),
+    );
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let path = hit_test(&layout, Position::new(50.0, 50.0));
+    assert_eq!(path, [layout.id(), layout.children()[1].id()]);
+
+    let path = hit_test(&layout, Position::new(10.0, 10.0));
+    assert_eq!(path, [layout.id(), layout.children()[0].id()]);
+}
+
+#[test]
+fn skip_non_interactive() {
+    let mut overlay = EmptyLayout::new().fill();
+    overlay.non_interactive = true;
+    let mut layout = StackLayout::new().fixed(100.0, 100.0);
+    layout.push(EmptyLayout::new().fill());
+    layout.push(overlay);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let point = Position::new(50.0, 50.0);
+    let overlay = layout.children()[1].id();
+    assert_eq!(hit_test(&layout, point), [layout.id(), overlay]);
+    assert_eq!(
+        hit_test_interactive(&layout, point),
+        [layout.id(), layout.children()[0].id()]
+    );
+}
+
+#[test]
+fn skip_non_interactive_children() {
+    let mut row = HorizontalLayout::new();
+    row.push(EmptyLayout::new().fixed(50.0, 50.0));
+    row.non_interactive = true;
+    let mut layout = VerticalLayout::new();
+    layout.push(row);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let path = hit_test_interactive(&layout, Position::new(10.0, 10.0));
+    assert_eq!(path, [layout.id()]);
+}
+
+#[test]
+fn hit_absolute_layout() {
+    let mut layout = HorizontalLayout::new().fixed(200.0, 100.0);
+    layout.push(EmptyLayout::new().fixed(50.0, 50.0));
+    layout.push(
+        AbsoluteLayout::new(EmptyLayout::new().fixed(40.0, 40.0))
+            .right(0.0)
+            .bottom(0.0),
+    );
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let absolute = &layout.children()[1];
+    let path = hit_test(&layout, Position::new(180.0, 80.0));
+    assert_eq!(
+        path,
+        [layout.id(), absolute.id(), absolute.children()[0].id()]
+    );
+}
+
+#[test]
+fn hit_child_outside_parent() {
+    let mut row = HorizontalLayout::new().fixed(100.0, 50.0);
+    row.push(AbsoluteLayout::new(EmptyLayout::new().fixed(40.0, 20.0)).left(120.0));
+    let mut layout = VerticalLayout::new();
+    layout.push(row);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let row = &layout.children()[0];
+    let absolute = &row.children()[0];
+    let child = &absolute.children()[0];
+    let path = hit_test(&layout, Position::new(130.0, 10.0));
+    assert_eq!(path, [layout.id(), row.id(), absolute.id(), child.id()]);
+    assert!(hit_test(&layout, Position::new(170.0, 10.0)).is_empty());
+}
+
+#[test]
+fn clipped_children_are_not_hit_outside_parent() {
+    let mut list = VerticalLayout::new();
+    for _ in 0..10 {
+        list.push(EmptyLayout::new().fixed(100.0, 50.0));
+    }
+    let mut layout = ScrollLayout::new(list).fixed(100.0, 200.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(hit_test(&layout, Position::new(10.0, 10.0)).len(), 3);
+    assert!(hit_test(&layout, Position::new(10.0, 300.0)).is_empty());
+}
@@ -1,4 +1,7 @@
-use flow::{BoxSizing, EmptyLayout, HorizontalLayout, Layout, Size, solve_layout};
+use flow::{
+    BoxSizing, CrossAxisAlignment, EmptyLayout, HorizontalLayout, Layout, MainAxisAlignment,
+    MeasuredLayout, Position, Size, solve_layout,
+};
 
 #[test]
 fn fit_children() {
@@ -14,9 +17,9 @@ fn fit_children() {
     root.push(child1);
     root.push(child2);
 
-    solve_layout(&mut root, Size::unit(500.0));
+    solve_layout(&mut root, Size::unit(500.0)).unwrap();
     let width = 90.0 + 350.0;
-    let height = 24.0 + 240.0;
+    let height = 240.0;
 
     assert_eq!(root.size(), Size::new(width, height))
 }
@@ -24,7 +27,7 @@ fn fit_children() {
 #[test]
 fn fill_window() {
     let mut root = HorizontalLayout::new().fill();
-    solve_layout(&mut root, Size::new(500.0, 100.0));
+    solve_layout(&mut root, Size::new(500.0, 100.0)).unwrap();
     assert_eq!(root.size(), Size::new(500.0, 100.0))
 }
 
@@ -36,8 +39,245 @@ fn fixed_with_flex_sizing() {
 
     let mut root = HorizontalLayout::new().fill();
     root.append([child1,child2,child3]);
-    solve_layout(&mut root, Size::new(700.0, 500.0));
+    solve_layout(&mut root, Size::new(700.0, 500.0)).unwrap();
 
     let child2 = &root.children()[0];
     assert_eq!(child2.size().width,200.0);
 }
+
+#[test]
+fn position_children_with_spacing_and_padding() {
+    let child1 = EmptyLayout::new().fixed(100.0, 20.0);
+    let child2 = EmptyLayout::new().fixed(50.0, 20.0);
+    let child3 = EmptyLayout::new().fixed(75.0, 20.0);
+
+    let mut root = HorizontalLayout::new().padding_left(10).padding_top(5);
+    root.spacing = 20;
+    root.append([child1, child2, child3]);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let children = root.children();
+    assert_eq!(children[0].position(), Position::new(10.0, 5.0));
+    assert_eq!(children[1].position(), Position::new(130.0, 5.0));
+    assert_eq!(children[2].position(), Position::new(200.0, 5.0));
+}
+
+#[test]
+fn position_nested_children() {
+    let mut inner = HorizontalLayout::new().padding_left(8);
+    inner.push(EmptyLayout::new().fixed(40.0, 40.0));
+
+    let mut root = HorizontalLayout::new().padding_left(12).padding_top(12);
+    root.push(EmptyLayout::new().fixed(100.0, 20.0));
+    root.push(inner);
+    root.set_position(Position::new(50.0, 50.0));
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let inner = &root.children()[1];
+    assert_eq!(inner.position(), Position::new(162.0, 62.0));
+}
+
+fn aligned_row(main: MainAxisAlignment, cross: CrossAxisAlignment) -> HorizontalLayout {
+    let mut root = HorizontalLayout::new()
+        .fixed(500.0, 100.0)
+        .main_axis_alignment(main)
+        .cross_axis_alignment(cross);
+    root.append([
+        EmptyLayout::new().fixed(100.0, 20.0),
+        EmptyLayout::new().fixed(100.0, 40.0),
+        EmptyLayout::new().fixed(100.0, 60.0),
+    ]);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+    root
+}
+
+fn x_positions(layout: &HorizontalLayout) -> Vec<f32> {
+    layout.children().iter().map(|c| c.position().x).collect()
+}
+
+fn y_positions(layout: &HorizontalLayout) -> Vec<f32> {
+    layout.children().iter().map(|c| c.position().y).collect()
+}
+
+#[test]
+fn main_axis_start() {
+    let root = aligned_row(MainAxisAlignment::Start, CrossAxisAlignment::Start);
+    assert_eq!(x_positions(&root), [0.0, 100.0, 200.0]);
+}
+
+#[test]
+fn main_axis_center() {
+    let root = aligned_row(MainAxisAlignment::Center, CrossAxisAlignment::Start);
+    assert_eq!(x_positions(&root), [100.0, 200.0, 300.0]);
+}
+
+#[test]
+fn main_axis_end() {
+    let root = aligned_row(MainAxisAlignment::End, CrossAxisAlignment::Start);
+    assert_eq!(x_positions(&root), [200.0, 300.0, 400.0]);
+}
+
+#[test]
+fn main_axis_space_evenly() {
+    let root = aligned_row(MainAxisAlignment::SpaceEvenly, CrossAxisAlignment::Start);
+    assert_eq!(x_positions(&root), [50.0, 200.0, 350.0]);
+}
+
+#[test]
+fn main_axis_space_between() {
+    let root = aligned_row(MainAxisAlignment::SpaceBetween, CrossAxisAlignment::Start);
+    assert_eq!(x_positions(&root), [0.0, 200.0, 400.0]);
+}
+
+#[test]
+fn main_axis_alignment_with_spacing_and_padding() {
+    let mut root = HorizontalLayout::new()
+        .fixed(500.0, 100.0)
+        .padding_left(20)
+        .padding_right(30)
+        .main_axis_alignment(MainAxisAlignment::End);
+    root.spacing = 10;
+    root.append([
+        EmptyLayout::new().fixed(100.0, 20.0),
+        EmptyLayout::new().fixed(100.0, 20.0),
+    ]);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(x_positions(&root), [260.0, 370.0]);
+}
+
+#[test]
+fn cross_axis_start() {
+    let root = aligned_row(MainAxisAlignment::Start, CrossAxisAlignment::Start);
+    assert_eq!(y_positions(&root), [0.0, 0.0, 0.0]);
+}
+
+#[test]
+fn cross_axis_center() {
+    let root = aligned_row(MainAxisAlignment::Start, CrossAxisAlignment::Center);
+    assert_eq!(y_positions(&root), [40.0, 30.0, 20.0]);
+}
+
+#[test]
+fn cross_axis_end() {
+    let root = aligned_row(MainAxisAlignment::Start, CrossAxisAlignment::End);
+    assert_eq!(y_positions(&root), [80.0, 60.0, 40.0]);
+}
+
+#[test]
+fn fit_with_padding_and_spacing() {
+    let mut root = HorizontalLayout::new()
+        .padding_left(10)
+        .padding_right(20)
+        .padding_top(5)
+        .padding_bottom(15);
+    root.spacing = 12;
+    root.append([
+        EmptyLayout::new().fixed(100.0, 40.0),
+        EmptyLayout::new().fixed(50.0, 80.0),
+        EmptyLayout::new().fixed(25.0, 20.0),
+    ]);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let width = 10.0 + 100.0 + 12.0 + 50.0 + 12.0 + 25.0 + 20.0;
+    let height = 5.0 + 80.0 + 15.0;
+    assert_eq!(root.size(), Size::new(width, height));
+}
+
+#[test]
+fn nested_fit_includes_padding() {
+    let mut inner = HorizontalLayout::new().padding_left(8).padding_right(8);
+    inner.push(EmptyLayout::new().fixed(40.0, 40.0));
+
+    let mut root = HorizontalLayout::new();
+    root.push(inner);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(root.size(), Size::new(56.0, 40.0));
+}
+
+#[test]
+fn flex_width_excludes_padding_and_spacing() {
+    let mut root = HorizontalLayout::new()
+        .fill()
+        .padding_left(20)
+        .padding_right(30);
+    root.spacing = 10;
+    root.append([
+        EmptyLayout::new().fixed_width(100.0),
+        EmptyLayout::new().fill_width(),
+        EmptyLayout::new().fill_width(),
+    ]);
+    solve_layout(&mut root, Size::new(500.0, 100.0)).unwrap();
+
+    let flex_width = (500.0 - 20.0 - 30.0 - 20.0 - 100.0) / 2.0;
+    let children = root.children();
+    assert_eq!(children[1].size().width, flex_width);
+    assert_eq!(children[2].size().width, flex_width);
+    assert_eq!(children[2].position().x + flex_width, 470.0);
+}
+
+#[test]
+fn flex_height_excludes_padding() {
+    let mut root = HorizontalLayout::new()
+        .fill()
+        .padding_top(10)
+        .padding_bottom(15);
+    root.push(EmptyLayout::new().fill());
+    solve_layout(&mut root, Size::new(500.0, 100.0)).unwrap();
+
+    assert_eq!(root.children()[0].size().height, 75.0);
+}
+
+#[test]
+fn percent_width_is_independent_of_siblings() {
+    let mut layout = HorizontalLayout::new()
+        .fixed_width(1000.0)
+        .padding_left(100);
+    layout.push(EmptyLayout::new().width_percent(30.0));
+    layout.push(EmptyLayout::new().fixed_width(200.0));
+    layout.push(EmptyLayout::new().fill_width());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().width, 270.0);
+    assert_eq!(layout.children()[2].size().width, 430.0);
+    assert_eq!(layout.children()[2].position().x, 570.0);
+}
+
+#[test]
+fn percent_height() {
+    let mut layout = HorizontalLayout::new().fixed(500.0, 200.0);
+    layout.push(EmptyLayout::new().fixed_width(50.0).height_percent(50.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().height, 100.0);
+}
+
+#[test]
+fn fit_includes_percent_children() {
+    let mut layout = HorizontalLayout::new();
+    layout.push(EmptyLayout::new().width_percent(10.0).fixed_height(20.0));
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size().width, 150.0);
+}
+
+#[test]
+fn fit_children_share_space() {
+    // 250 pixels of text, that can't be narrower than a 50 pixel word
+    let text = || {
+        MeasuredLayout::new(|constraints| {
+            let width = constraints.max_width.clamp(50.0, 250.0);
+            Size::new(width, (250.0 / width).ceil() * 20.0)
+        })
+    };
+    let mut layout = HorizontalLayout::new().fixed_width(300.0);
+    layout.push(text());
+    layout.push(text());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size(), Size::new(150.0, 40.0));
+    assert_eq!(layout.children()[1].size(), Size::new(150.0, 40.0));
+    assert_eq!(layout.children()[1].position().x, 150.0);
+}
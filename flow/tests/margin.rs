@@ -0,0 +1,129 @@
+use flow::{
+    Alignment, BlockLayout, BoxSizing, CrossAxisAlignment, EmptyLayout, GridLayout,
+    HorizontalLayout, Layout, Margin, Position, Size, StackLayout, VerticalLayout, WrapLayout,
+    solve_layout,
+};
+
+#[test]
+fn horizontal_margin_and_spacing() {
+    let mut layout = HorizontalLayout::new().padding_left(5);
+    layout.spacing = 10;
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0).margin_right(15));
+    layout.push(
+        EmptyLayout::new()
+            .fixed(50.0, 20.0)
+            .margin(Margin::new(5, 0, 10, 5)),
+    );
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(135.0, 35.0));
+    assert_eq!(layout.children()[0].position(), Position::new(5.0, 0.0));
+    assert_eq!(layout.children()[1].position(), Position::new(85.0, 10.0));
+}
+
+#[test]
+fn vertical_margin_and_spacing() {
+    let mut layout = VerticalLayout::new();
+    layout.spacing = 10;
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0).margin_bottom(10));
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0).margin(Margin::all(5)));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(60.0, 70.0));
+    assert_eq!(layout.children()[1].position(), Position::new(5.0, 45.0));
+}
+
+#[test]
+fn flex_space_excludes_margins() {
+    let mut layout = HorizontalLayout::new().fixed(400.0, 100.0);
+    layout.spacing = 20;
+    layout.push(EmptyLayout::new().fill().margin(Margin::sides(30, 10)));
+    layout.push(EmptyLayout::new().fill());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let first = &layout.children()[0];
+    let second = &layout.children()[1];
+    assert_eq!(first.size(), Size::new(160.0, 80.0));
+    assert_eq!(first.position(), Position::new(30.0, 10.0));
+    assert_eq!(second.size(), Size::new(160.0, 100.0));
+    assert_eq!(second.position(), Position::new(240.0, 0.0));
+}
+
+#[test]
+fn cross_axis_alignment_with_margin() {
+    let mut layout = VerticalLayout::new()
+        .fixed(100.0, 100.0)
+        .cross_axis_alignment(CrossAxisAlignment::Center);
+    layout.push(EmptyLayout::new().fixed(40.0, 20.0).margin_left(20));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].position().x, 40.0);
+}
+
+#[test]
+fn block_margin() {
+    let mut layout = BlockLayout::new(EmptyLayout::new().fill().margin(Margin::all(10)))
+        .fixed(100.0, 100.0)
+        .padding_top(5);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.child().size(), Size::new(80.0, 75.0));
+    assert_eq!(layout.child().position(), Position::new(10.0, 15.0));
+}
+
+#[test]
+fn fit_block_includes_margin() {
+    let mut layout = BlockLayout::new(EmptyLayout::new().fixed(50.0, 50.0).margin(Margin::all(8)));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::unit(66.0));
+}
+
+#[test]
+fn stack_margin() {
+    let mut layout = StackLayout::new().fixed(100.0, 100.0);
+    layout.place(
+        EmptyLayout::new().fixed(20.0, 20.0).margin(Margin::all(10)),
+        Alignment::BottomRight,
+        Position::default(),
+    );
+    layout.push(EmptyLayout::new().fill().margin_left(25));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].position(), Position::new(70.0, 70.0));
+    assert_eq!(layout.children()[1].size(), Size::new(75.0, 100.0));
+    assert_eq!(layout.children()[1].position(), Position::new(25.0, 0.0));
+}
+
+#[test]
+fn wrap_margin_and_spacing() {
+    let mut layout = WrapLayout::new().fixed_width(200.0);
+    layout.spacing = 10;
+    layout.run_spacing = 5;
+    for _ in 0..3 {
+        layout.push(
+            EmptyLayout::new()
+                .fixed(50.0, 20.0)
+                .margin(Margin::sides(10, 5)),
+        );
+    }
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    // Each child takes up 70 pixels, so only two fit in a run
+    assert_eq!(layout.size().height, 65.0);
+    assert_eq!(layout.children()[1].position(), Position::new(90.0, 5.0));
+    assert_eq!(layout.children()[2].position(), Position::new(10.0, 40.0));
+}
+
+#[test]
+fn grid_tracks_include_margin() {
+    let mut layout = GridLayout::new().columns([BoxSizing::Fit, BoxSizing::Fit]);
+    layout.column_gap = 10;
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0).margin_right(20));
+    layout.push(EmptyLayout::new().fixed(50.0, 20.0).margin_top(5));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.column_sizes(), [70.0, 50.0]);
+    assert_eq!(layout.row_sizes(), [25.0]);
+    assert_eq!(layout.children()[1].position(), Position::new(80.0, 5.0));
+}
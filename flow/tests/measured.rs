@@ -0,0 +1,56 @@
+use flow::{
+    BlockLayout, HorizontalLayout, Layout, MeasuredLayout, Size, VerticalLayout, solve_layout,
+};
+
+/// Text that is `width` pixels long on a single line, with lines
+/// 20 pixels tall
+fn text(width: f32) -> MeasuredLayout {
+    MeasuredLayout::new(move |constraints| {
+        let lines = (width / constraints.max_width).ceil().max(1.0);
+        Size::new(width.min(constraints.max_width), lines * 20.0)
+    })
+}
+
+#[test]
+fn fit_measured_size() {
+    let mut layout = BlockLayout::new(text(150.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.size(), Size::new(150.0, 20.0));
+}
+
+#[test]
+fn wrap_to_max_width() {
+    let mut layout = BlockLayout::new(text(250.0)).fixed_width(100.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.child().size(), Size::new(100.0, 60.0));
+}
+
+#[test]
+fn flex_width_measures_height() {
+    let mut layout = HorizontalLayout::new().fixed_width(300.0);
+    layout.push(text(400.0).fill_width());
+    layout.push(text(400.0).fill_width());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size(), Size::new(150.0, 60.0));
+    assert_eq!(layout.size().height, 60.0);
+}
+
+#[test]
+fn fixed_size_ignores_measure() {
+    let mut layout = VerticalLayout::new();
+    layout.push(text(400.0).fixed(50.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size(), Size::new(50.0, 20.0));
+}
+
+#[test]
+fn measure_respects_bounds() {
+    let mut layout = BlockLayout::new(text(150.0).max_width(100.0).min_height(30.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.child().size(), Size::new(100.0, 40.0));
+}
@@ -0,0 +1,126 @@
+use flow::{
+    Axis, BlockLayout, BoxSizing, EmptyLayout, HorizontalLayout, Layout, LayoutError,
+    LayoutWarning, Size, VerticalLayout, solve_layout,
+};
+
+#[test]
+fn no_warnings_when_children_fit() {
+    let mut layout = HorizontalLayout::new().fixed_width(500.0);
+    layout.push(EmptyLayout::new().fixed(200.0, 50.0));
+    layout.push(EmptyLayout::new().fill_width());
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn fixed_children_overflow_parent() {
+    let mut layout = HorizontalLayout::new().fixed(300.0, 100.0);
+    layout.push(EmptyLayout::new().fixed(200.0, 50.0));
+    layout.push(EmptyLayout::new().fixed(150.0, 50.0));
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(
+        warnings,
+        [LayoutWarning::Overflow {
+            id: layout.children()[1].id(),
+            parent: layout.id(),
+            axis: Axis::Horizontal,
+            amount: 50.0,
+        }]
+    );
+}
+
+#[test]
+fn flex_children_are_not_negative() {
+    let mut layout = HorizontalLayout::new().fixed(300.0, 100.0);
+    layout.push(EmptyLayout::new().fixed(400.0, 50.0));
+    layout.push(EmptyLayout::new().fill_width());
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[1].size().width, 0.0);
+    assert_eq!(warnings.len(), 2);
+}
+
+#[test]
+fn cross_axis_overflow() {
+    let mut layout = VerticalLayout::new().fixed(100.0, 300.0);
+    layout.push(EmptyLayout::new().fixed(120.0, 50.0));
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(
+        warnings,
+        [LayoutWarning::Overflow {
+            id: layout.children()[0].id(),
+            parent: layout.id(),
+            axis: Axis::Horizontal,
+            amount: 20.0,
+        }]
+    );
+}
+
+#[test]
+fn nested_overflow() {
+    let mut inner = HorizontalLayout::new().fixed(50.0, 50.0);
+    inner.push(EmptyLayout::new().fixed(80.0, 10.0));
+    let inner_id = inner.id();
+
+    let mut layout = VerticalLayout::new();
+    layout.push(inner);
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(warnings.len(), 1);
+    let LayoutWarning::Overflow { parent, .. } = warnings[0];
+    assert_eq!(parent, inner_id);
+}
+
+#[test]
+fn large_flex_factors() {
+    let mut layout = HorizontalLayout::new().fixed_width(400.0);
+    let mut child = EmptyLayout::new();
+    child.intrinsic_width = BoxSizing::Flex(200.0);
+    layout.push(child);
+    let mut child = EmptyLayout::new();
+    child.intrinsic_width = BoxSizing::Flex(200.0);
+    layout.push(child);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().width, 200.0);
+}
+
+#[test]
+fn padding_larger_than_layout() {
+    let mut layout = BlockLayout::new(EmptyLayout::new().fill())
+        .fixed(10.0, 10.0)
+        .padding_left(20);
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.child().size().width, 0.0);
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn negative_fixed_size() {
+    let mut layout = HorizontalLayout::new();
+    layout.push(EmptyLayout::new().fixed(-10.0, 10.0));
+    let error = solve_layout(&mut layout, Size::unit(1000.0)).unwrap_err();
+
+    assert_eq!(
+        error,
+        LayoutError::NegativeSize {
+            id: layout.children()[0].id(),
+            axis: Axis::Horizontal,
+            size: -10.0,
+        }
+    );
+}
+
+#[test]
+fn warning_message() {
+    let mut layout = HorizontalLayout::new().fixed(100.0, 100.0);
+    layout.push(EmptyLayout::new().fixed(150.0, 50.0));
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let message = warnings[0].to_string();
+    assert!(message.contains("by 50 along the horizontal axis"));
+}
@@ -0,0 +1,157 @@
+use flow::{
+    Axis, BlockLayout, BoxSizing, EmptyLayout, GridLayout, HorizontalLayout, Layout, Position,
+    ScrollLayout, Size, StackLayout, VerticalLayout, solve_layout,
+};
+
+fn list(count: usize) -> VerticalLayout {
+    let mut list = VerticalLayout::new();
+    for _ in 0..count {
+        list.push(EmptyLayout::new().fixed(100.0, 50.0));
+    }
+    list
+}
+
+#[test]
+fn content_is_not_constrained() {
+    let mut layout = ScrollLayout::new(list(10)).fixed(100.0, 200.0);
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert!(warnings.is_empty());
+    assert_eq!(layout.size(), Size::new(100.0, 200.0));
+    assert_eq!(layout.content_size(), Size::new(100.0, 500.0));
+    assert_eq!(layout.max_offset(), 300.0);
+}
+
+#[test]
+fn offset_moves_child() {
+    let mut layout = ScrollLayout::new(list(10)).fixed(100.0, 200.0);
+    layout.set_position(Position::new(10.0, 20.0));
+    layout.set_offset(120.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let list = layout.child();
+    assert_eq!(list.position(), Position::new(10.0, -100.0));
+    assert_eq!(list.children()[3].position(), Position::new(10.0, 50.0));
+    assert_eq!(
+        layout.visible_rect(),
+        (Position::new(0.0, 120.0), Size::new(100.0, 200.0))
+    );
+}
+
+#[test]
+fn scroll_after_solving() {
+    let mut layout = ScrollLayout::new(list(10)).fixed(100.0, 200.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    layout.scroll_by(50.0);
+    layout.scroll_by(50.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.offset(), 100.0);
+    assert_eq!(layout.child().children()[2].position().y, 0.0);
+}
+
+#[test]
+fn scroll_nested_layout() {
+    let scroll = ScrollLayout::new(list(10)).fixed(100.0, 200.0);
+    let id = scroll.id();
+    let mut layout = VerticalLayout::new();
+    layout.push(EmptyLayout::new().fixed(100.0, 20.0));
+    layout.push(scroll);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let scroll = layout.find(id).unwrap();
+    assert_eq!(scroll.scroll_offset(), Some(0.0));
+    assert_eq!(scroll.max_scroll_offset(), Some(300.0));
+    assert_eq!(layout.scroll_offset(), None);
+    assert_eq!(layout.max_scroll_offset(), None);
+
+    layout.find_mut(id).unwrap().set_scroll_offset(120.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let scroll = layout.find(id).unwrap();
+    let list = scroll.children()[0];
+    assert_eq!(scroll.scroll_offset(), Some(120.0));
+    assert_eq!(list.position(), Position::new(0.0, -100.0));
+    assert_eq!(list.children()[3].position(), Position::new(0.0, 50.0));
+}
+
+#[test]
+fn clamp_offset() {
+    let mut layout = ScrollLayout::new(list(10)).fixed(100.0, 200.0);
+    layout.set_offset(1000.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    assert_eq!(layout.offset(), 300.0);
+
+    layout.set_offset(-20.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    assert_eq!(layout.offset(), 0.0);
+}
+
+#[test]
+fn short_content_does_not_scroll() {
+    let mut layout = ScrollLayout::new(list(2)).fixed(100.0, 200.0);
+    layout.set_offset(50.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.max_offset(), 0.0);
+    assert_eq!(layout.offset(), 0.0);
+}
+
+#[test]
+fn fit_viewport_grows_to_max() {
+    let mut short = BlockLayout::new(ScrollLayout::new(list(2))).fixed(100.0, 300.0);
+    let mut long = BlockLayout::new(ScrollLayout::new(list(10))).fixed(100.0, 300.0);
+    solve_layout(&mut short, Size::unit(1000.0)).unwrap();
+    solve_layout(&mut long, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(short.child().size(), Size::new(100.0, 100.0));
+    assert_eq!(long.child().size(), Size::new(100.0, 300.0));
+}
+
+#[test]
+fn fit_viewport_in_vertical_layout() {
+    let mut layout = VerticalLayout::new().fixed(100.0, 300.0);
+    layout.push(EmptyLayout::new().fixed(100.0, 50.0));
+    layout.push(ScrollLayout::new(list(10)));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[1].size(), Size::new(100.0, 250.0));
+}
+
+#[test]
+fn fit_viewport_in_stack_and_grid() {
+    let mut stack = StackLayout::new().fixed(100.0, 300.0);
+    stack.push(ScrollLayout::new(list(10)));
+    let mut grid = GridLayout::new().rows([BoxSizing::Fixed(300.0)]);
+    grid.push(ScrollLayout::new(list(10)));
+    solve_layout(&mut stack, Size::unit(1000.0)).unwrap();
+    solve_layout(&mut grid, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(stack.children()[0].size(), Size::new(100.0, 300.0));
+    assert_eq!(grid.children()[0].size(), Size::new(100.0, 300.0));
+}
+
+#[test]
+fn horizontal_scroll() {
+    let mut row = HorizontalLayout::new();
+    for _ in 0..5 {
+        row.push(EmptyLayout::new().fixed(100.0, 50.0));
+    }
+    let mut layout = ScrollLayout::new(row)
+        .axis(Axis::Horizontal)
+        .fixed(200.0, 50.0);
+    layout.set_offset(150.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.max_offset(), 300.0);
+    assert_eq!(layout.child().position(), Position::new(-150.0, 0.0));
+}
+
+#[test]
+fn flex_child_fills_viewport() {
+    let mut layout = ScrollLayout::new(EmptyLayout::new().fill()).fixed(100.0, 200.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.content_size(), Size::new(100.0, 200.0));
+}
@@ -0,0 +1,100 @@
+use flow::{
+    BoxConstraints, EmptyLayout, HorizontalLayout, MeasuredLayout, Position, Size, solve_layout,
+};
+
+#[test]
+fn no_shrink_by_default() {
+    let mut layout = HorizontalLayout::new().fixed_width(300.0);
+    layout.push(EmptyLayout::new().fixed(200.0, 20.0));
+    layout.push(EmptyLayout::new().fixed(200.0, 20.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    // Shrinking is opt-in, so the children keep their fixed widths
+    assert_eq!(layout.children()[1].size().width, 200.0);
+    assert_eq!(layout.shrunk_width(), 0.0);
+    assert_eq!(layout.overflow_width(), 100.0);
+}
+
+#[test]
+fn shrink_fixed_children() {
+    let mut layout = HorizontalLayout::new().fixed_width(300.0);
+    layout.spacing = 20;
+    layout.push(EmptyLayout::new().fixed(240.0, 20.0).shrink(1.0));
+    layout.push(EmptyLayout::new().fixed(80.0, 20.0).shrink(1.0));
+    layout.push(EmptyLayout::new().fill_width());
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert!(warnings.is_empty());
+    assert_eq!(layout.children()[0].size().width, 195.0);
+    assert_eq!(layout.children()[1].size().width, 65.0);
+    assert_eq!(layout.children()[1].position(), Position::new(215.0, 0.0));
+    assert_eq!(layout.children()[2].size().width, 0.0);
+    assert_eq!(layout.shrunk_width(), 60.0);
+    assert_eq!(layout.overflow_width(), 0.0);
+}
+
+#[test]
+fn larger_factor_gives_up_more() {
+    let mut layout = HorizontalLayout::new().fixed_width(300.0);
+    layout.push(EmptyLayout::new().fixed(200.0, 20.0).shrink(1.0));
+    layout.push(EmptyLayout::new().fixed(200.0, 20.0).shrink(3.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().width, 175.0);
+    assert_eq!(layout.children()[1].size().width, 125.0);
+}
+
+#[test]
+fn shrink_stops_at_min_width() {
+    let mut layout = HorizontalLayout::new().fixed_width(300.0);
+    layout.push(
+        EmptyLayout::new()
+            .fixed(200.0, 20.0)
+            .min_width(180.0)
+            .shrink(1.0),
+    );
+    layout.push(EmptyLayout::new().fixed(200.0, 20.0).shrink(1.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().width, 180.0);
+    assert_eq!(layout.children()[1].size().width, 120.0);
+}
+
+#[test]
+fn overflow_when_min_reached() {
+    let mut layout = HorizontalLayout::new().fixed_width(300.0);
+    layout.push(
+        EmptyLayout::new()
+            .fixed(200.0, 20.0)
+            .min_width(190.0)
+            .shrink(1.0),
+    );
+    layout.push(
+        EmptyLayout::new()
+            .fixed(200.0, 20.0)
+            .min_width(150.0)
+            .shrink(1.0),
+    );
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.shrunk_width(), 60.0);
+    assert_eq!(layout.overflow_width(), 40.0);
+}
+
+#[test]
+fn fit_text_shrinks_to_min_content() {
+    // Five words that are 40 pixels wide, wrapped to the max width
+    let text = MeasuredLayout::new(|constraints: BoxConstraints| {
+        let per_line = (constraints.max_width / 40.0).floor().clamp(1.0, 5.0);
+        let lines = (5.0 / per_line).ceil();
+        Size::new(per_line * 40.0, lines * 20.0)
+    });
+
+    let mut layout = HorizontalLayout::new().fixed_width(100.0);
+    layout.push(EmptyLayout::new().fixed(80.0, 20.0));
+    layout.push(text.shrink(1.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[1].size(), Size::new(40.0, 100.0));
+    assert_eq!(layout.overflow_width(), 20.0);
+}
@@ -0,0 +1,113 @@
+use flow::{
+    EmptyLayout, HorizontalLayout, Layout, Position, Size, VerticalLayout, count_recomputed,
+    solve_layout,
+};
+
+fn thirds(width: f32) -> HorizontalLayout {
+    let mut layout = HorizontalLayout::new().fixed(width, 20.0);
+    for _ in 0..3 {
+        layout.push(EmptyLayout::new().fill());
+    }
+    layout
+}
+
+/// Check that every child starts where the previous child ended
+fn assert_no_gaps(layout: &HorizontalLayout) {
+    let mut x = layout.position().x;
+    for child in layout.children() {
+        assert_eq!(child.position().x, x);
+        x += child.size().width;
+    }
+    assert_eq!(x, layout.position().x + layout.size().width);
+}
+
+#[test]
+fn unsnapped_by_default() {
+    let mut layout = thirds(1000.0);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().width, 1000.0 / 3.0);
+}
+
+#[test]
+fn snap_flex_children() {
+    let mut layout = thirds(1000.0);
+    layout.set_scale_factor(Some(1.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let widths: Vec<f32> = layout.children().iter().map(|c| c.size().width).collect();
+    assert_eq!(widths, [333.0, 334.0, 333.0]);
+    assert_no_gaps(&layout);
+}
+
+#[test]
+fn snap_to_device_pixels() {
+    let mut layout = thirds(100.0);
+    layout.set_position(Position::new(0.3, 0.0));
+    layout.set_scale_factor(Some(2.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.position().x, 0.5);
+    assert_eq!(layout.children()[1].position().x, 33.5);
+    assert_eq!(layout.children()[1].size().width, 33.5);
+    assert_no_gaps(&layout);
+}
+
+#[test]
+fn snap_whole_tree() {
+    let mut layout = VerticalLayout::new().fixed_width(700.0);
+    layout.spacing = 3;
+    for width in [700.0, 350.0, 233.0] {
+        let mut row = thirds(width);
+        row.spacing = 1;
+        layout.push(row);
+    }
+    layout.set_scale_factor(Some(1.0));
+    let warnings = solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert!(warnings.is_empty());
+    assert_eq!(layout.size().width, 700.0);
+    flow::visit(&layout, &mut |l, _| {
+        let (position, size) = (l.position(), l.size());
+        for value in [position.x, position.y, size.width, size.height] {
+            assert_eq!(value, value.round());
+        }
+    });
+}
+
+#[test]
+fn children_fill_snapped_parent() {
+    let mut layout = VerticalLayout::new().fixed(100.0, 99.9);
+    for _ in 0..3 {
+        let mut row = HorizontalLayout::new().fill();
+        row.push(EmptyLayout::new().fill());
+        layout.push(row);
+    }
+    layout.set_scale_factor(Some(1.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let row = &layout.children()[1];
+    assert_eq!(row.position().y, 33.0);
+    assert_eq!(row.size().height, 34.0);
+    for row in layout.children() {
+        let child = &row.children()[0];
+        assert_eq!(child.position(), row.position());
+        assert_eq!(child.size(), row.size());
+    }
+}
+
+#[test]
+fn snapped_layouts_are_cached() {
+    let mut layout = thirds(1000.0);
+    layout.set_scale_factor(Some(1.0));
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(count_recomputed(&layout), 0);
+    assert_no_gaps(&layout);
+
+    layout.set_scale_factor(None);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+    assert_eq!(count_recomputed(&layout), 4);
+    assert_eq!(layout.children()[1].size().width, 1000.0 / 3.0);
+}
@@ -0,0 +1,107 @@
+use flow::{
+    Alignment, EmptyLayout, HorizontalLayout, Layout, Position, Size, StackLayout, solve_layout,
+};
+
+fn anchored(alignment: Alignment) -> Position<f32> {
+    let mut stack = StackLayout::new().fixed(200.0, 100.0);
+    stack.place(
+        EmptyLayout::new().fixed(20.0, 10.0),
+        alignment,
+        Position::default(),
+    );
+    solve_layout(&mut stack, Size::unit(1000.0)).unwrap();
+    stack.children()[0].position()
+}
+
+#[test]
+fn fit_largest_child() {
+    let mut stack = StackLayout::new();
+    stack.push(EmptyLayout::new().fixed(100.0, 20.0));
+    stack.push(EmptyLayout::new().fixed(40.0, 80.0));
+    solve_layout(&mut stack, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(stack.size(), Size::new(100.0, 80.0));
+}
+
+#[test]
+fn fit_with_padding() {
+    let mut stack = StackLayout::new().padding_left(10).padding_top(5);
+    stack.push(EmptyLayout::new().fixed(100.0, 20.0));
+    solve_layout(&mut stack, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(stack.size(), Size::new(110.0, 25.0));
+    assert_eq!(stack.children()[0].position(), Position::new(10.0, 5.0));
+}
+
+#[test]
+fn flex_children_fill_stack() {
+    let mut stack = StackLayout::new();
+    stack.push(EmptyLayout::new().fill());
+    stack.push(EmptyLayout::new().fixed(120.0, 60.0));
+    solve_layout(&mut stack, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(stack.children()[0].size(), Size::new(120.0, 60.0));
+}
+
+#[test]
+fn flex_children_fill_flex_stack() {
+    let mut stack = StackLayout::new().fill();
+    stack.push(EmptyLayout::new().fill_width().fixed_height(20.0));
+    solve_layout(&mut stack, Size::new(300.0, 200.0)).unwrap();
+
+    assert_eq!(stack.children()[0].size(), Size::new(300.0, 20.0));
+}
+
+#[test]
+fn nine_anchor_points() {
+    assert_eq!(anchored(Alignment::TopLeft), Position::new(0.0, 0.0));
+    assert_eq!(anchored(Alignment::TopCenter), Position::new(90.0, 0.0));
+    assert_eq!(anchored(Alignment::TopRight), Position::new(180.0, 0.0));
+    assert_eq!(anchored(Alignment::CenterLeft), Position::new(0.0, 45.0));
+    assert_eq!(anchored(Alignment::Center), Position::new(90.0, 45.0));
+    assert_eq!(anchored(Alignment::CenterRight), Position::new(180.0, 45.0));
+    assert_eq!(anchored(Alignment::BottomLeft), Position::new(0.0, 90.0));
+    assert_eq!(anchored(Alignment::BottomCenter), Position::new(90.0, 90.0));
+    assert_eq!(anchored(Alignment::BottomRight), Position::new(180.0, 90.0));
+}
+
+#[test]
+fn offset_from_anchor() {
+    let mut stack = StackLayout::new().fixed(200.0, 100.0);
+    stack.place(
+        EmptyLayout::new().fixed(20.0, 20.0),
+        Alignment::BottomRight,
+        Position::new(-8.0, -8.0),
+    );
+    solve_layout(&mut stack, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(stack.children()[0].position(), Position::new(172.0, 72.0));
+}
+
+#[test]
+fn nested_stack_position() {
+    let mut stack = StackLayout::new();
+    stack.push(EmptyLayout::new().fixed(50.0, 50.0));
+    stack.place(
+        EmptyLayout::new().fixed(10.0, 10.0),
+        Alignment::Center,
+        Position::default(),
+    );
+
+    let mut root = HorizontalLayout::new();
+    root.push(EmptyLayout::new().fixed(100.0, 50.0));
+    root.push(stack);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(root.children()[1].size(), Size::unit(50.0));
+    assert_eq!(root.size(), Size::new(150.0, 50.0));
+}
+
+#[test]
+fn percent_children_are_relative_to_stack() {
+    let mut stack = StackLayout::new().fixed(200.0, 100.0);
+    stack.push(EmptyLayout::new().width_percent(50.0).height_percent(10.0));
+    solve_layout(&mut stack, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(stack.children()[0].size(), Size::new(100.0, 10.0));
+}
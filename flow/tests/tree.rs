@@ -0,0 +1,259 @@
+use flow::{
+    BoxSizing, CrossAxisAlignment, EmptyLayout, HorizontalLayout, LayoutTree, MainAxisAlignment,
+    Node, Padding, Position, Size, TreeError, VerticalLayout, solve_layout,
+};
+
+#[test]
+fn insert_children() {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::vertical());
+    let a = tree.insert_child(root, Node::empty()).unwrap();
+    let b = tree.insert_child(root, Node::empty()).unwrap();
+
+    assert_eq!(tree.len(), 3);
+    assert_eq!(tree.get(root).unwrap().children(), [a, b]);
+    assert_eq!(tree.get(a).unwrap().parent(), Some(root));
+}
+
+#[test]
+fn empty_nodes_cannot_have_children() {
+    let mut tree = LayoutTree::new();
+    let leaf = tree.insert(Node::empty());
+
+    assert_eq!(
+        tree.insert_child(leaf, Node::empty()),
+        Err(TreeError::NotAContainer(leaf))
+    );
+}
+
+#[test]
+fn find_by_global_id() {
+    let mut tree = LayoutTree::new();
+    let node = Node::horizontal();
+    let global_id = node.id();
+    let id = tree.insert(node);
+
+    assert_eq!(tree.find(global_id), Some(id));
+    tree.remove(id).unwrap();
+    assert_eq!(tree.find(global_id), None);
+}
+
+#[test]
+fn cloned_nodes_get_new_ids() {
+    let mut tree = LayoutTree::new();
+    let node = Node::horizontal();
+    let a = tree.insert(node.clone());
+    let b = tree.insert(node);
+
+    let global_a = tree.get(a).unwrap().id();
+    let global_b = tree.get(b).unwrap().id();
+    assert_ne!(global_a, global_b);
+    assert_eq!(tree.find(global_a), Some(a));
+    assert_eq!(tree.find(global_b), Some(b));
+    tree.remove(b).unwrap();
+    assert_eq!(tree.find(global_a), Some(a));
+}
+
+#[test]
+fn remove_subtree() {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::vertical());
+    let row = tree.insert_child(root, Node::horizontal()).unwrap();
+    let leaf = tree.insert_child(row, Node::empty()).unwrap();
+    let other = tree.insert_child(root, Node::empty()).unwrap();
+
+    let removed = tree.remove(row).unwrap();
+
+    assert_eq!(removed.parent(), None);
+    assert_eq!(tree.len(), 2);
+    assert!(tree.get(leaf).is_none());
+    assert_eq!(tree.get(root).unwrap().children(), [other]);
+    assert_eq!(tree.remove(row).unwrap_err(), TreeError::NotFound(row));
+}
+
+#[test]
+fn remove_root() {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::vertical());
+    tree.set_root(root).unwrap();
+    tree.remove(root).unwrap();
+
+    assert_eq!(tree.root(), None);
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn reparent_node() {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::vertical());
+    let first = tree.insert_child(root, Node::horizontal()).unwrap();
+    let second = tree.insert_child(root, Node::horizontal()).unwrap();
+    let leaf = tree.insert_child(first, Node::empty()).unwrap();
+
+    tree.reparent(leaf, second).unwrap();
+
+    assert!(tree.get(first).unwrap().children().is_empty());
+    assert_eq!(tree.get(second).unwrap().children(), [leaf]);
+    assert_eq!(tree.get(leaf).unwrap().parent(), Some(second));
+}
+
+#[test]
+fn reparent_into_own_subtree() {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::vertical());
+    let row = tree.insert_child(root, Node::horizontal()).unwrap();
+
+    assert_eq!(
+        tree.reparent(root, row),
+        Err(TreeError::Cycle {
+            child: root,
+            parent: row
+        })
+    );
+    assert_eq!(
+        tree.reparent(row, row),
+        Err(TreeError::Cycle {
+            child: row,
+            parent: row
+        })
+    );
+}
+
+#[test]
+fn solve_without_root() {
+    let mut tree = LayoutTree::new();
+    tree.insert(Node::vertical());
+
+    assert!(tree.solve(Size::unit(500.0)).unwrap().is_empty());
+}
+
+#[test]
+fn solve_like_layouts() {
+    let mut tree = LayoutTree::new();
+    let mut root = Node::vertical()
+        .fill()
+        .padding_top(10)
+        .cross_axis_alignment(CrossAxisAlignment::Center);
+    root.spacing = 20;
+    let root = tree.insert(root);
+    tree.set_root(root).unwrap();
+    let mut row = Node::horizontal()
+        .fill_width()
+        .main_axis_alignment(MainAxisAlignment::SpaceBetween);
+    row.padding = Padding::all(5);
+    let row = tree.insert_child(root, row).unwrap();
+    let a = tree
+        .insert_child(row, Node::empty().fixed(100.0, 40.0))
+        .unwrap();
+    let b = tree
+        .insert_child(row, Node::empty().width_percent(25.0).fixed_height(20.0))
+        .unwrap();
+    let c = tree
+        .insert_child(root, Node::empty().fixed_width(200.0).fill_height())
+        .unwrap();
+    tree.solve(Size::new(800.0, 600.0)).unwrap();
+
+    let mut layout = VerticalLayout::new()
+        .fill()
+        .padding_top(10)
+        .cross_axis_alignment(CrossAxisAlignment::Center);
+    layout.spacing = 20;
+    let mut inner = HorizontalLayout::new()
+        .fill_width()
+        .main_axis_alignment(MainAxisAlignment::SpaceBetween);
+    inner.padding = Padding::all(5);
+    inner.push(EmptyLayout::new().fixed(100.0, 40.0));
+    inner.push(EmptyLayout::new().width_percent(25.0).fixed_height(20.0));
+    layout.push(inner);
+    layout.push(EmptyLayout::new().fixed_width(200.0).fill_height());
+    solve_layout(&mut layout, Size::new(800.0, 600.0)).unwrap();
+
+    let inner = &layout.children()[0];
+    let expected = [
+        (row, inner.size(), inner.position()),
+        (
+            a,
+            inner.children()[0].size(),
+            inner.children()[0].position(),
+        ),
+        (
+            b,
+            inner.children()[1].size(),
+            inner.children()[1].position(),
+        ),
+        (
+            c,
+            layout.children()[1].size(),
+            layout.children()[1].position(),
+        ),
+    ];
+    for (id, size, position) in expected {
+        let node = tree.get(id).unwrap();
+        assert_eq!(node.size(), size);
+        assert_eq!(node.position(), position);
+    }
+}
+
+#[test]
+fn fit_and_clamped_flex_children() {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::horizontal().fixed(300.0, 50.0));
+    tree.set_root(root).unwrap();
+    let column = tree.insert_child(root, Node::vertical()).unwrap();
+    tree.insert_child(column, Node::empty().fixed(120.0, 20.0))
+        .unwrap();
+    let capped = tree
+        .insert_child(root, Node::empty().fill().max_width(60.0))
+        .unwrap();
+    let fill = tree.insert_child(root, Node::empty().fill()).unwrap();
+    tree.solve(Size::unit(1000.0)).unwrap();
+
+    let mut layout = HorizontalLayout::new().fixed(300.0, 50.0);
+    let mut inner = VerticalLayout::new();
+    inner.push(EmptyLayout::new().fixed(120.0, 20.0));
+    layout.push(inner);
+    layout.push(EmptyLayout::new().fill().max_width(60.0));
+    layout.push(EmptyLayout::new().fill());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    for (id, child) in [column, capped, fill].into_iter().zip(layout.children()) {
+        assert_eq!(tree.get(id).unwrap().size(), child.size());
+        assert_eq!(tree.get(id).unwrap().position(), child.position());
+    }
+    assert_eq!(tree.get(fill).unwrap().size().width, 120.0);
+}
+
+#[test]
+fn report_overflow() {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::horizontal().fixed(100.0, 50.0));
+    tree.set_root(root).unwrap();
+    let mut child = Node::empty();
+    child.intrinsic_width = BoxSizing::Fixed(150.0);
+    tree.insert_child(root, child).unwrap();
+
+    let warnings = tree.solve(Size::unit(1000.0)).unwrap();
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn solve_large_tree() {
+    let mut tree = LayoutTree::new();
+    let root = tree.insert(Node::vertical());
+    tree.set_root(root).unwrap();
+    for _ in 0..100 {
+        let row = tree.insert_child(root, Node::horizontal()).unwrap();
+        for _ in 0..100 {
+            tree.insert_child(row, Node::empty().fixed(10.0, 10.0))
+                .unwrap();
+        }
+    }
+    tree.solve(Size::unit(1000.0)).unwrap();
+
+    assert_eq!(tree.len(), 10101);
+    let last = *tree.get(root).unwrap().children().last().unwrap();
+    assert_eq!(
+        tree.get(last).unwrap().position(),
+        Position::new(0.0, 990.0)
+    );
+}
@@ -0,0 +1,120 @@
+use flow::{
+    BoxSizing, CrossAxisAlignment, EmptyLayout, HorizontalLayout, Layout, MainAxisAlignment,
+    Position, Size, VerticalLayout, solve_layout,
+};
+
+#[test]
+fn fit_children() {
+    let mut child1 = EmptyLayout::new();
+    let mut child2 = EmptyLayout::new();
+
+    child1.intrinsic_width = BoxSizing::Fixed(90.0);
+    child1.intrinsic_height = BoxSizing::Fixed(24.0);
+    child2.intrinsic_width = BoxSizing::Fixed(350.0);
+    child2.intrinsic_height = BoxSizing::Fixed(240.0);
+
+    let mut root = VerticalLayout::new();
+    root.push(child1);
+    root.push(child2);
+
+    solve_layout(&mut root, Size::unit(500.0)).unwrap();
+    let width = 350.0;
+    let height = 24.0 + 240.0;
+
+    assert_eq!(root.size(), Size::new(width, height))
+}
+
+#[test]
+fn fill_window() {
+    let mut root = VerticalLayout::new().fill();
+    solve_layout(&mut root, Size::new(500.0, 100.0)).unwrap();
+    assert_eq!(root.size(), Size::new(500.0, 100.0))
+}
+
+#[test]
+fn fixed_with_flex_sizing() {
+    let child1 = EmptyLayout::new().fixed_height(200.0);
+    let child2 = EmptyLayout::new().fill_height();
+    let child3 = EmptyLayout::new().fixed_height(300.0);
+
+    let mut root = VerticalLayout::new().fill();
+    root.append([child1, child2, child3]);
+    solve_layout(&mut root, Size::new(500.0, 700.0)).unwrap();
+
+    let child2 = &root.children()[1];
+    assert_eq!(child2.size().height, 200.0);
+}
+
+#[test]
+fn position_children_with_spacing_and_padding() {
+    let child1 = EmptyLayout::new().fixed(20.0, 100.0);
+    let child2 = EmptyLayout::new().fixed(20.0, 50.0);
+    let child3 = EmptyLayout::new().fixed(20.0, 75.0);
+
+    let mut root = VerticalLayout::new().padding_left(5).padding_top(10);
+    root.spacing = 20;
+    root.append([child1, child2, child3]);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let children = root.children();
+    assert_eq!(children[0].position(), Position::new(5.0, 10.0));
+    assert_eq!(children[1].position(), Position::new(5.0, 130.0));
+    assert_eq!(children[2].position(), Position::new(5.0, 200.0));
+}
+
+#[test]
+fn main_axis_alignment() {
+    let mut root = VerticalLayout::new()
+        .fixed(100.0, 500.0)
+        .main_axis_alignment(MainAxisAlignment::SpaceBetween)
+        .cross_axis_alignment(CrossAxisAlignment::End);
+    root.append([
+        EmptyLayout::new().fixed(20.0, 100.0),
+        EmptyLayout::new().fixed(40.0, 100.0),
+    ]);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let children = root.children();
+    assert_eq!(children[0].position(), Position::new(80.0, 0.0));
+    assert_eq!(children[1].position(), Position::new(60.0, 400.0));
+}
+
+#[test]
+fn fit_with_padding_and_spacing() {
+    let mut root = VerticalLayout::new().padding_left(10).padding_top(5);
+    root.spacing = 12;
+    root.append([
+        EmptyLayout::new().fixed(40.0, 100.0),
+        EmptyLayout::new().fixed(80.0, 50.0),
+    ]);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(root.size(), Size::new(90.0, 167.0));
+}
+
+#[test]
+fn percent_height_is_independent_of_siblings() {
+    let mut layout = VerticalLayout::new().fixed_height(400.0);
+    layout.push(EmptyLayout::new().height_percent(25.0));
+    layout.push(EmptyLayout::new().fill_height());
+    layout.push(EmptyLayout::new().fill_height());
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(layout.children()[0].size().height, 100.0);
+    assert_eq!(layout.children()[1].size().height, 150.0);
+    assert_eq!(layout.children()[2].position().y, 250.0);
+}
+
+#[test]
+fn fit_row_uses_content_size() {
+    let mut row = HorizontalLayout::new();
+    row.push(EmptyLayout::new().fixed(100.0, 20.0));
+    let mut layout = VerticalLayout::new();
+    layout.push(row);
+    solve_layout(&mut layout, Size::unit(1000.0)).unwrap();
+
+    let row = &layout.children()[0];
+    assert_eq!(row.size(), Size::new(100.0, 20.0));
+    assert_eq!(row.constraints().max_width, 100.0);
+    assert_eq!(row.constraints().max_height, 20.0);
+}
@@ -0,0 +1,140 @@
+use flow::{
+    CrossAxisAlignment, EmptyLayout, Layout, MainAxisAlignment, Position, Size, WrapLayout,
+    solve_layout,
+};
+
+fn chips() -> [EmptyLayout; 4] {
+    [
+        EmptyLayout::new().fixed(100.0, 20.0),
+        EmptyLayout::new().fixed(100.0, 40.0),
+        EmptyLayout::new().fixed(100.0, 30.0),
+        EmptyLayout::new().fixed(150.0, 30.0),
+    ]
+}
+
+fn positions(layout: &WrapLayout) -> Vec<Position<f32>> {
+    layout.children().iter().map(|c| c.position()).collect()
+}
+
+#[test]
+fn fit_height_of_runs() {
+    let mut root = WrapLayout::new().fixed_width(320.0);
+    root.spacing = 10;
+    root.run_spacing = 5;
+    root.append(chips());
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    // [100, 100, 100] [150]
+    assert_eq!(root.size(), Size::new(320.0, 40.0 + 5.0 + 30.0));
+}
+
+#[test]
+fn fit_width_of_widest_run() {
+    let mut root = WrapLayout::new();
+    root.spacing = 10;
+    root.append(chips());
+    solve_layout(&mut root, Size::unit(300.0)).unwrap();
+
+    // [100, 100] [100, 150]
+    assert_eq!(root.size(), Size::new(260.0, 70.0));
+}
+
+#[test]
+fn wrap_with_padding() {
+    let mut root = WrapLayout::new()
+        .fixed_width(240.0)
+        .padding_left(20)
+        .padding_right(20)
+        .padding_top(10)
+        .padding_bottom(10);
+    root.append(chips());
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    // [100, 100] [100] [150]
+    assert_eq!(root.size().height, 40.0 + 30.0 + 30.0 + 20.0);
+    assert_eq!(
+        positions(&root),
+        [
+            Position::new(20.0, 10.0),
+            Position::new(120.0, 10.0),
+            Position::new(20.0, 50.0),
+            Position::new(20.0, 80.0),
+        ]
+    );
+}
+
+#[test]
+fn position_runs() {
+    let mut root = WrapLayout::new().fixed_width(320.0);
+    root.spacing = 10;
+    root.run_spacing = 5;
+    root.append(chips());
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(
+        positions(&root),
+        [
+            Position::new(0.0, 0.0),
+            Position::new(110.0, 0.0),
+            Position::new(220.0, 0.0),
+            Position::new(0.0, 45.0),
+        ]
+    );
+}
+
+#[test]
+fn main_axis_alignment_per_run() {
+    let mut root = WrapLayout::new()
+        .fixed_width(300.0)
+        .main_axis_alignment(MainAxisAlignment::End);
+    root.append(chips());
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    // [100, 100, 100] [150]
+    let positions = positions(&root);
+    assert_eq!(positions[0].x, 0.0);
+    assert_eq!(positions[3].x, 150.0);
+}
+
+#[test]
+fn main_axis_center_per_run() {
+    let mut root = WrapLayout::new()
+        .fixed_width(300.0)
+        .main_axis_alignment(MainAxisAlignment::Center);
+    root.append(chips());
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    assert_eq!(positions(&root)[3].x, 75.0);
+}
+
+#[test]
+fn cross_axis_alignment_per_run() {
+    let mut root = WrapLayout::new()
+        .fixed_width(300.0)
+        .cross_axis_alignment(CrossAxisAlignment::End);
+    root.append(chips());
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let positions = positions(&root);
+    assert_eq!(positions[0].y, 20.0);
+    assert_eq!(positions[1].y, 0.0);
+    assert_eq!(positions[2].y, 10.0);
+    assert_eq!(positions[3].y, 40.0);
+}
+
+#[test]
+fn flex_width_takes_full_run() {
+    let mut root = WrapLayout::new().fixed_width(300.0);
+    root.append([
+        EmptyLayout::new().fixed(100.0, 20.0),
+        EmptyLayout::new().fill_width().fixed_height(20.0),
+        EmptyLayout::new().fixed(100.0, 20.0),
+    ]);
+    solve_layout(&mut root, Size::unit(1000.0)).unwrap();
+
+    let children = root.children();
+    assert_eq!(children[1].size().width, 300.0);
+    assert_eq!(children[1].position(), Position::new(0.0, 20.0));
+    assert_eq!(children[2].position(), Position::new(0.0, 40.0));
+    assert_eq!(root.size().height, 60.0);
+}